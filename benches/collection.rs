@@ -0,0 +1,64 @@
+//! Benchmark object collection queries over the largest sample projects.
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use xcodeproj::pbxproj::{PBXRootObject, PBXTarget};
+
+const SAMPLES: [&str; 2] = ["demo2", "demo4"];
+
+fn load(name: &str) -> PBXRootObject {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/samples")
+        .join(format!("{name}.pbxproj"));
+    PBXRootObject::try_from(path).unwrap()
+}
+
+fn collection(c: &mut Criterion) {
+    for name in SAMPLES {
+        let project = load(name);
+        let target_name = project.targets()[0].name.unwrap().clone();
+        let mut group = c.benchmark_group(name);
+
+        group.bench_function("targets (full scan)", |b| {
+            b.iter(|| {
+                project.get_vec_by::<PBXTarget>(|(_, v)| {
+                    v.get_kind("isa")
+                        .map(|k| k.is_pbx_target())
+                        .unwrap_or_default()
+                })
+            })
+        });
+        group.bench_function("targets", |b| b.iter(|| project.targets()));
+        group.bench_function("files", |b| b.iter(|| project.files()));
+        group.bench_function("groups", |b| b.iter(|| project.groups()));
+        group.bench_function("get_target_by_name", |b| {
+            b.iter(|| project.get_target_by_name(&target_name))
+        });
+        group.bench_function("cached get_target_by_name", |b| {
+            let cache = project.cache();
+            b.iter(|| cache.get_target_by_name(&target_name).is_some())
+        });
+        group.bench_function("file_membership_index", |b| {
+            b.iter(|| project.file_membership_index("/").orphan_files().len())
+        });
+        group.bench_function("remove build files", |b| {
+            let ids = project
+                .build_files()
+                .into_iter()
+                .map(|file| file.id)
+                .collect::<HashSet<_>>();
+            b.iter_batched(
+                || load(name),
+                |mut project| {
+                    project.objects_mut().remove_all(&ids);
+                    project
+                },
+                BatchSize::LargeInput,
+            )
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, collection);
+criterion_main!(benches);
@@ -7,7 +7,7 @@ use phf_codegen::Map;
 
 fn main() {
     let out_dir = env::var("OUT_DIR").unwrap();
-    let ref mut f = File::create(Path::new(&out_dir).join("file_types.rs")).unwrap();
+    let f = &mut File::create(Path::new(&out_dir).join("file_types.rs")).unwrap();
     let mut map: Map<&str> = phf_codegen::Map::new();
     let txt = include_str!("./res/file_type.txt");
 
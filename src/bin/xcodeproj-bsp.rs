@@ -0,0 +1,15 @@
+//! Serve Build Server Protocol over stdio for a given `.xcodeproj`
+//!
+//! Usage: `xcodeproj-bsp <path/to/Project.xcodeproj> [configuration]`
+use anyhow::{anyhow, Result};
+use xcodeproj::bsp::BuildServer;
+
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    let xcodeproj_folder = args.next().ok_or_else(|| {
+        anyhow!("Usage: xcodeproj-bsp <path/to/Project.xcodeproj> [configuration]")
+    })?;
+    let configuration = args.next().unwrap_or_else(|| "Debug".into());
+
+    BuildServer::new(xcodeproj_folder, &configuration)?.serve_stdio()
+}
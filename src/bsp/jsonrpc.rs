@@ -0,0 +1,78 @@
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+
+/// JSON-RPC error code for unknown methods
+pub(crate) const METHOD_NOT_FOUND: i64 = -32601;
+/// JSON-RPC error code for invalid method parameters
+pub(crate) const INVALID_PARAMS: i64 = -32602;
+/// JSON-RPC error code for internal errors
+pub(crate) const INTERNAL_ERROR: i64 = -32603;
+
+/// Read a single `Content-Length` framed message. Returns `None` on EOF.
+pub(crate) fn read_message<R: BufRead>(reader: &mut R) -> Result<Option<Value>> {
+    let mut content_length = None;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                content_length = Some(value.trim().parse::<usize>()?);
+            }
+        }
+    }
+
+    let content_length = content_length.context("Missing Content-Length header")?;
+    let mut content = vec![0; content_length];
+    reader.read_exact(&mut content)?;
+
+    Ok(Some(serde_json::from_slice(&content)?))
+}
+
+/// Write a single `Content-Length` framed message.
+pub(crate) fn write_message<W: Write>(writer: &mut W, message: &Value) -> Result<()> {
+    let content = serde_json::to_string(message)?;
+    write!(writer, "Content-Length: {}\r\n\r\n{content}", content.len())?;
+    writer.flush()?;
+    Ok(())
+}
+
+pub(crate) fn response(id: Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+pub(crate) fn error_response(id: Value, code: i64, message: String) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+pub(crate) fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let message = notification("build/initialized", json!({}));
+        let mut buffer = vec![];
+        write_message(&mut buffer, &message).unwrap();
+        write_message(&mut buffer, &message).unwrap();
+
+        let mut reader = std::io::Cursor::new(buffer);
+        assert_eq!(read_message(&mut reader).unwrap(), Some(message.clone()));
+        assert_eq!(read_message(&mut reader).unwrap(), Some(message));
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+    }
+}
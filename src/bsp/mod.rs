@@ -0,0 +1,670 @@
+//! Build Server Protocol (BSP) server backed by [`XCodeProject`]
+//!
+//! Serves targets, sources, dependency sources and compiler options over stdio, so that
+//! sourcekit-lsp and other BSP clients can work without Xcode running. The served
+//! `project.pbxproj` is watched for changes and clients are notified through
+//! `buildTarget/didChange`.
+//!
+//! NOTE: Changes are detected by polling the modification time of `project.pbxproj` every
+//! second while idle, so a change may take up to a second to be picked up, and changes within
+//! the timestamp resolution of the file system can be missed.
+mod jsonrpc;
+mod types;
+
+pub use types::*;
+
+use crate::pbxproj::{PBXProductType, PBXTarget};
+use crate::{CompileCommands, XCodeProject};
+use anyhow::{Context, Result};
+use jsonrpc::*;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeSet, HashMap};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, SystemTime};
+
+const LANGUAGE_IDS: [&str; 5] = ["swift", "c", "cpp", "objective-c", "objective-cpp"];
+
+/// BSP server serving a single `.xcodeproj` with a given build configuration
+#[derive(Debug)]
+pub struct BuildServer {
+    xcodeproj_folder: PathBuf,
+    configuration: String,
+    project: XCodeProject,
+    last_modified: Option<SystemTime>,
+    compile_commands: HashMap<String, CompileCommands>,
+    source_packages: Option<PathBuf>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TargetsParams {
+    targets: Vec<BuildTargetIdentifier>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TextDocumentIdentifier {
+    uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TextDocumentParams {
+    text_document: TextDocumentIdentifier,
+    target: Option<BuildTargetIdentifier>,
+}
+
+impl BuildServer {
+    /// Interval at which `project.pbxproj` modification time is polled for changes
+    const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// Create new build server from xcodeproj_folder and configuration name (e.g. `Debug`)
+    pub fn new<P: AsRef<Path>>(xcodeproj_folder: P, configuration: &str) -> Result<Self> {
+        let xcodeproj_folder = xcodeproj_folder.as_ref().to_path_buf();
+        let project = XCodeProject::new(&xcodeproj_folder)?;
+        let last_modified = Self::modified(&xcodeproj_folder);
+        let source_packages = derived_source_packages(project.name());
+
+        Ok(Self {
+            xcodeproj_folder,
+            configuration: configuration.to_string(),
+            project,
+            last_modified,
+            compile_commands: Default::default(),
+            source_packages,
+        })
+    }
+
+    /// Use a given `SourcePackages` directory for dependency sources, i.e. one passed to
+    /// `xcodebuild -clonedSourcePackagesDirPath`.
+    ///
+    /// By default, `SourcePackages` of the most recently built copy of the project in Xcode
+    /// derived data is used.
+    #[must_use]
+    pub fn with_source_packages<P: AsRef<Path>>(mut self, source_packages: P) -> Self {
+        self.source_packages = Some(source_packages.as_ref().to_path_buf());
+        self
+    }
+
+    /// Get a reference to the served xcode project.
+    #[must_use]
+    pub fn project(&self) -> &XCodeProject {
+        &self.project
+    }
+
+    /// Serve over stdin and stdout until `build/exit` or stdin is closed
+    pub fn serve_stdio(self) -> Result<()> {
+        self.serve(BufReader::new(std::io::stdin()), std::io::stdout())
+    }
+
+    /// Serve over given reader and writer until `build/exit` or reader is closed
+    pub fn serve<R, W>(mut self, reader: R, mut writer: W) -> Result<()>
+    where
+        R: BufRead + Send + 'static,
+        W: Write,
+    {
+        let (sender, receiver) = mpsc::channel();
+
+        std::thread::spawn(move || {
+            let mut reader = reader;
+            loop {
+                match read_message(&mut reader) {
+                    Ok(Some(message)) => {
+                        if sender.send(message).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(None) => break,
+                    Err(err) => {
+                        tracing::error!("Failed to read message: {err}");
+                        break;
+                    }
+                }
+            }
+        });
+
+        loop {
+            match receiver.recv_timeout(Self::WATCH_INTERVAL) {
+                Ok(message) => {
+                    let is_exit = message.get("method") == Some(&json!("build/exit"));
+                    if let Some(response) = self.handle(message) {
+                        write_message(&mut writer, &response)?;
+                    }
+                    if is_exit {
+                        break;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+
+            if let Some(notification) = self.reload_if_changed() {
+                write_message(&mut writer, &notification)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handle a single JSON-RPC message, returning a response for requests.
+    pub fn handle(&mut self, message: Value) -> Option<Value> {
+        let method = message.get("method")?.as_str()?;
+        let id = message.get("id").cloned();
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        let result = match method {
+            "build/initialize" => Ok(self.initialize()),
+            "build/shutdown" => Ok(Value::Null),
+            "workspace/buildTargets" => self.build_targets().map(|t| json!({ "targets": t })),
+            "workspace/reload" => self.reload().map(|_| Value::Null),
+            "buildTarget/sources" => self.sources(params).map(|i| json!({ "items": i })),
+            "buildTarget/inverseSources" => self
+                .inverse_sources(params)
+                .map(|t| json!({ "targets": t })),
+            "buildTarget/dependencySources" => self
+                .dependency_sources(params)
+                .map(|i| json!({ "items": i })),
+            "textDocument/sourceKitOptions" => self.source_kit_options(params),
+            method if id.is_none() => {
+                tracing::trace!("Ignoring notification {method:?}");
+                return None;
+            }
+            method => {
+                let message = format!("Unknown method {method:?}");
+                return Some(error_response(id?, METHOD_NOT_FOUND, message));
+            }
+        };
+
+        Some(match result {
+            Ok(result) => response(id?, result),
+            Err(err) if err.is::<serde_json::Error>() => {
+                error_response(id?, INVALID_PARAMS, format!("{err:#}"))
+            }
+            Err(err) => error_response(id?, INTERNAL_ERROR, format!("{err:#}")),
+        })
+    }
+
+    /// Reload project if `project.pbxproj` changed, returning `buildTarget/didChange`
+    /// notification on success.
+    pub fn reload_if_changed(&mut self) -> Option<Value> {
+        let modified = Self::modified(&self.xcodeproj_folder);
+        if modified == self.last_modified {
+            return None;
+        }
+
+        self.last_modified = modified;
+        if let Err(err) = self.reload() {
+            tracing::error!("Failed to reload {:?}: {err:#}", self.xcodeproj_folder);
+            return None;
+        }
+
+        let changes = self
+            .project
+            .targets()
+            .iter()
+            .flat_map(|target| Some(json!({ "target": self.identifier(target.name?), "kind": 2 })))
+            .collect::<Vec<_>>();
+
+        Some(notification(
+            "buildTarget/didChange",
+            json!({ "changes": changes }),
+        ))
+    }
+
+    fn modified(xcodeproj_folder: &Path) -> Option<SystemTime> {
+        std::fs::metadata(xcodeproj_folder.join("project.pbxproj"))
+            .and_then(|m| m.modified())
+            .ok()
+    }
+
+    fn reload(&mut self) -> Result<()> {
+        self.project = XCodeProject::new(&self.xcodeproj_folder)?;
+        self.compile_commands.clear();
+        Ok(())
+    }
+
+    fn initialize(&self) -> Value {
+        json!({
+            "displayName": "xcodeproj",
+            "version": env!("CARGO_PKG_VERSION"),
+            "bspVersion": "2.1.0",
+            "capabilities": {
+                "languageIds": LANGUAGE_IDS,
+                "dependencySourcesProvider": true,
+                "inverseSourcesProvider": true,
+                "buildTargetChangedProvider": true,
+            },
+            "dataKind": "sourceKit",
+            "data": { "sourceKitOptionsProvider": true },
+        })
+    }
+
+    fn build_targets(&self) -> Result<Vec<BuildTarget>> {
+        let base_directory = path_to_uri(self.project.source_root());
+
+        self.project
+            .targets()
+            .iter()
+            .filter(|target| target.name.is_some())
+            .map(|target| {
+                let (tags, capabilities) = match target.product_type {
+                    PBXProductType::UnitTestBundle
+                    | PBXProductType::UiTestBundle
+                    | PBXProductType::OcUnitTestBundle => ("test", (true, false)),
+                    PBXProductType::Application
+                    | PBXProductType::CommandLineTool
+                    | PBXProductType::MessagesApplication
+                    | PBXProductType::WatchApp
+                    | PBXProductType::Watch2App
+                    | PBXProductType::Watch2AppContainer => ("application", (false, true)),
+                    PBXProductType::Framework
+                    | PBXProductType::StaticFramework
+                    | PBXProductType::DynamicLibrary
+                    | PBXProductType::StaticLibrary => ("library", (false, false)),
+                    _ => ("", (false, false)),
+                };
+
+                let mut language_ids = self
+                    .target_sources(target)
+                    .iter()
+                    .flat_map(|path| language_id(path))
+                    .collect::<Vec<_>>();
+                language_ids.sort_unstable();
+                language_ids.dedup();
+
+                Ok(BuildTarget {
+                    id: self.identifier(target.name.context("Target name")?),
+                    display_name: target.name.cloned(),
+                    base_directory: Some(base_directory.clone()),
+                    tags: [tags]
+                        .into_iter()
+                        .filter(|t| !t.is_empty())
+                        .map(String::from)
+                        .collect(),
+                    language_ids: language_ids.into_iter().map(String::from).collect(),
+                    dependencies: target
+                        .target_dependencies
+                        .iter()
+                        .flat_map(|d| Some(self.identifier(d.target.as_ref()?.name?)))
+                        .collect(),
+                    capabilities: BuildTargetCapabilities {
+                        can_compile: true,
+                        can_test: capabilities.0,
+                        can_run: capabilities.1,
+                        can_debug: false,
+                    },
+                })
+            })
+            .collect()
+    }
+
+    fn sources(&self, params: Value) -> Result<Vec<SourcesItem>> {
+        let params: TargetsParams = serde_json::from_value(params)?;
+
+        params
+            .targets
+            .into_iter()
+            .map(|identifier| {
+                let name = target_name(&identifier.uri)?;
+                let target = self.get_target(&name)?;
+                let sources = self
+                    .target_sources(&target)
+                    .into_iter()
+                    .map(|path| SourceItem {
+                        uri: path_to_uri(path),
+                        kind: 1,
+                        generated: false,
+                    })
+                    .collect();
+
+                Ok(SourcesItem {
+                    target: identifier,
+                    sources,
+                })
+            })
+            .collect()
+    }
+
+    fn inverse_sources(&self, params: Value) -> Result<Vec<BuildTargetIdentifier>> {
+        let params: TextDocumentParams = serde_json::from_value(params)?;
+        let path = uri_to_path(&params.text_document.uri)?;
+
+        Ok(self
+            .project
+            .targets()
+            .iter()
+            .filter(|target| self.target_sources(target).contains(&path))
+            .flat_map(|target| Some(self.identifier(target.name?)))
+            .collect())
+    }
+
+    fn dependency_sources(&self, params: Value) -> Result<Vec<DependencySourcesItem>> {
+        let params: TargetsParams = serde_json::from_value(params)?;
+
+        params
+            .targets
+            .into_iter()
+            .map(|identifier| {
+                let name = target_name(&identifier.uri)?;
+                let target = self.get_target(&name)?;
+                let sources = target
+                    .package_product_dependencies
+                    .iter()
+                    .flat_map(|d| self.checkout(d.package.as_ref()?.repository_url?))
+                    .map(path_to_uri)
+                    .collect::<BTreeSet<_>>();
+
+                Ok(DependencySourcesItem {
+                    target: identifier,
+                    sources: sources.into_iter().collect(),
+                })
+            })
+            .collect()
+    }
+
+    /// Get checked out sources of a package with a given repository url, if any
+    fn checkout(&self, repository_url: &str) -> Option<PathBuf> {
+        let url = repository_url.trim().trim_end_matches('/');
+        let name = url
+            .strip_suffix(".git")
+            .unwrap_or(url)
+            .rsplit(['/', ':'])
+            .next()?;
+        let checkouts = self.source_packages.as_ref()?.join("checkouts");
+
+        std::fs::read_dir(checkouts)
+            .ok()?
+            .flatten()
+            .find(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .eq_ignore_ascii_case(name)
+            })
+            .map(|entry| entry.path())
+    }
+
+    fn source_kit_options(&mut self, params: Value) -> Result<Value> {
+        let params: TextDocumentParams = serde_json::from_value(params)?;
+        let path = uri_to_path(&params.text_document.uri)?;
+        let target_name = match params.target {
+            Some(identifier) => target_name(&identifier.uri)?,
+            None => match self.project.targets().iter().find_map(|target| {
+                self.target_sources(target)
+                    .contains(&path)
+                    .then(|| target.name.cloned())
+                    .flatten()
+            }) {
+                Some(name) => name,
+                None => return Ok(Value::Null),
+            },
+        };
+
+        if !self.compile_commands.contains_key(&target_name) {
+            let commands = self
+                .project
+                .compile_commands(&target_name, &self.configuration)?;
+            self.compile_commands.insert(target_name.clone(), commands);
+        }
+
+        let command = match self.compile_commands[&target_name]
+            .iter()
+            .find(|command| command.file == path)
+        {
+            Some(command) => command,
+            None => return Ok(Value::Null),
+        };
+
+        Ok(serde_json::to_value(SourceKitOptions {
+            compiler_arguments: command.arguments[1..].to_vec(),
+            working_directory: Some(command.directory.to_string_lossy().to_string()),
+        })?)
+    }
+
+    fn get_target<'a>(&'a self, name: &'a str) -> Result<PBXTarget<'a>> {
+        self.project
+            .get_target_by_name(name)
+            .ok_or_else(|| anyhow::anyhow!("No target found with {name:?}"))
+    }
+
+    /// Source and header files of a given target
+    fn target_sources(&self, target: &PBXTarget) -> Vec<PathBuf> {
+        let source_root = self.project.source_root();
+        target
+            .build_phases
+            .iter()
+            .filter(|phase| phase.is_sources() || phase.is_headers())
+            .flat_map(|phase| phase.files.iter())
+            .flat_map(|file| {
+                file.file
+                    .as_ref()?
+                    .full_path(&self.project, &source_root)
+                    .ok()
+            })
+            .collect()
+    }
+
+    fn identifier(&self, target_name: &str) -> BuildTargetIdentifier {
+        BuildTargetIdentifier {
+            uri: format!(
+                "xcode://{}/{}",
+                percent_encode(self.project.name()),
+                percent_encode(target_name)
+            ),
+        }
+    }
+}
+
+/// Find `SourcePackages` of a project with a given name in Xcode derived data, where
+/// directories are named after the project followed by a hash.
+fn derived_source_packages(name: &str) -> Option<PathBuf> {
+    let derived_data =
+        PathBuf::from(std::env::var_os("HOME")?).join("Library/Developer/Xcode/DerivedData");
+
+    std::fs::read_dir(derived_data)
+        .ok()?
+        .flatten()
+        .filter(|entry| {
+            let file_name = entry.file_name();
+            let file_name = file_name.to_string_lossy();
+            file_name.rsplit_once('-').map(|(project, _)| project) == Some(name)
+        })
+        .map(|entry| entry.path().join("SourcePackages"))
+        .filter(|path| path.join("checkouts").is_dir())
+        .max_by_key(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+}
+
+fn language_id(path: &Path) -> Option<&'static str> {
+    match path.extension()?.to_str()? {
+        "swift" => Some("swift"),
+        "c" | "h" => Some("c"),
+        "cpp" | "cc" | "cxx" | "c++" | "hpp" => Some("cpp"),
+        "m" => Some("objective-c"),
+        "mm" => Some("objective-cpp"),
+        _ => None,
+    }
+}
+
+fn target_name(uri: &str) -> Result<String> {
+    uri.strip_prefix("xcode://")
+        .and_then(|rest| rest.split_once('/'))
+        .map(|(_, name)| percent_decode(name))
+        .ok_or_else(|| anyhow::anyhow!("Invalid build target uri {uri:?}"))
+}
+
+fn path_to_uri<P: AsRef<Path>>(path: P) -> String {
+    format!(
+        "file://{}",
+        percent_encode(&path.as_ref().to_string_lossy())
+    )
+}
+
+fn uri_to_path(uri: &str) -> Result<PathBuf> {
+    uri.strip_prefix("file://")
+        .map(|path| PathBuf::from(percent_decode(path)))
+        .ok_or_else(|| anyhow::anyhow!("Expected file uri, got {uri:?}"))
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                (b as char).to_string()
+            }
+            b => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+
+        match (bytes[i], hex) {
+            (b'%', Some(b)) => {
+                decoded.push(b);
+                i += 3;
+            }
+            (b, _) => {
+                decoded.push(b);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serve a sample from a temporary directory, removed once returned guard is dropped
+    fn setup(sample: &str) -> (tempfile::TempDir, BuildServer) {
+        let root = tempfile::tempdir().unwrap();
+        let xcodeproj_folder = root.path().join("Demo.xcodeproj");
+        std::fs::create_dir_all(&xcodeproj_folder).unwrap();
+        std::fs::copy(
+            format!(
+                "{}/tests/samples/{sample}.pbxproj",
+                env!("CARGO_MANIFEST_DIR")
+            ),
+            xcodeproj_folder.join("project.pbxproj"),
+        )
+        .unwrap();
+
+        let server = BuildServer::new(xcodeproj_folder, "Debug").unwrap();
+        (root, server)
+    }
+
+    fn request(server: &mut BuildServer, method: &str, params: Value) -> Value {
+        let message = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        server.handle(message).unwrap()
+    }
+
+    #[test]
+    fn build_targets() {
+        let (_root, mut server) = setup("demo8");
+        let response = request(&mut server, "build/initialize", json!({}));
+        assert_eq!(response["result"]["displayName"], "xcodeproj");
+
+        let response = request(&mut server, "workspace/buildTargets", Value::Null);
+        let targets: Vec<BuildTarget> =
+            serde_json::from_value(response["result"]["targets"].clone()).unwrap();
+        let tests = targets
+            .iter()
+            .find(|t| t.display_name.as_deref() == Some("testTests"))
+            .unwrap();
+
+        assert_eq!(tests.id.uri, "xcode://Demo/testTests");
+        assert_eq!(tests.tags, vec!["test"]);
+        assert_eq!(tests.language_ids, vec!["objective-c"]);
+        assert_eq!(tests.dependencies[0].uri, "xcode://Demo/test");
+    }
+
+    #[test]
+    fn sources_and_options() {
+        let (_root, mut server) = setup("demo1");
+        let root = server.project().source_root();
+        let response = request(
+            &mut server,
+            "buildTarget/sources",
+            json!({ "targets": [{ "uri": "xcode://Demo/Wordle" }] }),
+        );
+        let items: Vec<SourcesItem> =
+            serde_json::from_value(response["result"]["items"].clone()).unwrap();
+        assert_eq!(items[0].sources.len(), 12);
+
+        let uri = path_to_uri(root.join("Source/Views/GuessView.swift"));
+        let response = request(
+            &mut server,
+            "textDocument/sourceKitOptions",
+            json!({ "textDocument": { "uri": uri }, "language": "swift" }),
+        );
+        let options: SourceKitOptions = serde_json::from_value(response["result"].clone()).unwrap();
+        assert_eq!(options.compiler_arguments[..2], ["-sdk", "iphoneos"]);
+
+        let response = request(
+            &mut server,
+            "buildTarget/inverseSources",
+            json!({ "textDocument": { "uri": uri } }),
+        );
+        assert_eq!(
+            response["result"]["targets"][0]["uri"],
+            "xcode://Demo/Wordle"
+        );
+
+        let params = json!({ "targets": [{ "uri": "xcode://Demo/Wordle" }] });
+        let checkout = root.join("SourcePackages/checkouts/swift-log");
+        std::fs::create_dir_all(&checkout).unwrap();
+        let mut server = server.with_source_packages(root.join("SourcePackages"));
+        let response = request(&mut server, "buildTarget/dependencySources", params);
+        assert_eq!(
+            response["result"]["items"][0]["sources"],
+            json!([path_to_uri(&checkout)])
+        );
+    }
+
+    #[test]
+    fn unknown_method_and_notifications() {
+        let (_root, mut server) = setup("demo1");
+        let response = request(&mut server, "unknown/method", Value::Null);
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        let response = request(&mut server, "buildTarget/sources", json!({}));
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+
+        let notification = json!({ "jsonrpc": "2.0", "method": "build/initialized" });
+        assert_eq!(server.handle(notification), None);
+    }
+
+    #[test]
+    fn did_change_on_reload() {
+        let (_root, mut server) = setup("demo1");
+        assert_eq!(server.reload_if_changed(), None);
+
+        server.last_modified = None;
+        let notification = server.reload_if_changed().unwrap();
+        assert_eq!(notification["method"], "buildTarget/didChange");
+        assert_eq!(
+            notification["params"]["changes"][0]["target"]["uri"],
+            "xcode://Demo/Wordle"
+        );
+    }
+
+    #[test]
+    fn uri_round_trip() {
+        let path = PathBuf::from("/path/to/My Project/File+Extension.swift");
+        assert_eq!(uri_to_path(&path_to_uri(&path)).unwrap(), path);
+        assert_eq!(target_name("xcode://Demo/My%20App").unwrap(), "My App");
+    }
+}
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+/// Unique identifier of a build target (`xcode://<project>/<target>`)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct BuildTargetIdentifier {
+    /// Target uri
+    pub uri: String,
+}
+
+/// Clients can use these capabilities to notify users what BSP endpoints can and cannot be used
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildTargetCapabilities {
+    /// This target can be compiled by the BSP server.
+    pub can_compile: bool,
+    /// This target can be tested by the BSP server.
+    pub can_test: bool,
+    /// This target can be run by the BSP server.
+    pub can_run: bool,
+    /// This target can be debugged by the BSP server.
+    pub can_debug: bool,
+}
+
+/// Build target as described by [`PBXTarget`]
+///
+/// [`PBXTarget`]: crate::pbxproj::PBXTarget
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildTarget {
+    /// Target identifier
+    pub id: BuildTargetIdentifier,
+    /// Target name
+    pub display_name: Option<String>,
+    /// Directory where target sources are resolved from
+    pub base_directory: Option<String>,
+    /// Target tags, i.e. `application`, `library` or `test`
+    pub tags: Vec<String>,
+    /// Languages of target sources
+    pub language_ids: Vec<String>,
+    /// Direct dependencies of the target
+    pub dependencies: Vec<BuildTargetIdentifier>,
+    /// Target capabilities
+    pub capabilities: BuildTargetCapabilities,
+}
+
+/// Source file or directory belonging to a build target
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SourceItem {
+    /// File uri
+    pub uri: String,
+    /// `1` for file, `2` for directory
+    pub kind: u8,
+    /// Whether the source is generated by the build
+    pub generated: bool,
+}
+
+/// Sources of a build target
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SourcesItem {
+    /// Target identifier
+    pub target: BuildTargetIdentifier,
+    /// Target sources
+    pub sources: Vec<SourceItem>,
+}
+
+/// Dependency sources of a build target
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DependencySourcesItem {
+    /// Target identifier
+    pub target: BuildTargetIdentifier,
+    /// Dependency source uris
+    pub sources: Vec<String>,
+}
+
+/// Compiler options of a given file, as requested by `textDocument/sourceKitOptions`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceKitOptions {
+    /// Compiler arguments without the compiler executable
+    pub compiler_arguments: Vec<String>,
+    /// Working directory for compiler arguments
+    pub working_directory: Option<String>,
+}
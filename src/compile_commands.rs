@@ -0,0 +1,424 @@
+use crate::pbxproj::{
+    PBXObjectCollection, PBXTarget, PBXTargetPlatform, PBXValue, XCBuildSettings,
+};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Single entry of a clang compilation database.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CompileCommand {
+    /// The working directory of the compilation.
+    pub directory: PathBuf,
+    /// The main translation unit source processed by this compilation step.
+    pub file: PathBuf,
+    /// The compile command argv as list of strings.
+    pub arguments: Vec<String>,
+}
+
+/// Compilation database (`compile_commands.json`) of a [`PBXTarget`] for a given configuration.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize, derive_deref_rs::Deref)]
+#[serde(transparent)]
+pub struct CompileCommands(Vec<CompileCommand>);
+
+/// Source file language as far as compile commands are concerned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SourceLanguage {
+    Swift,
+    C,
+    ObjectiveC,
+    Cpp,
+    ObjectiveCpp,
+}
+
+impl SourceLanguage {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "swift" => Some(Self::Swift),
+            "c" => Some(Self::C),
+            "m" => Some(Self::ObjectiveC),
+            "cpp" | "cc" | "cxx" | "c++" => Some(Self::Cpp),
+            "mm" => Some(Self::ObjectiveCpp),
+            _ => None,
+        }
+    }
+
+    fn clang_language(&self) -> &str {
+        match self {
+            Self::Swift => "swift",
+            Self::C => "c",
+            Self::ObjectiveC => "objective-c",
+            Self::Cpp => "c++",
+            Self::ObjectiveCpp => "objective-c++",
+        }
+    }
+
+    fn is_objc(&self) -> bool {
+        matches!(self, Self::ObjectiveC | Self::ObjectiveCpp)
+    }
+
+    fn is_cpp(&self) -> bool {
+        matches!(self, Self::Cpp | Self::ObjectiveCpp)
+    }
+}
+
+impl CompileCommands {
+    /// Generate compile commands for target's sources with resolved build settings of
+    /// `configuration`. `source_root` is the directory containing the `.xcodeproj`.
+    pub fn new<P: AsRef<Path>>(
+        objects: &PBXObjectCollection,
+        target: &PBXTarget,
+        configuration: &str,
+        source_root: P,
+    ) -> Result<Self> {
+        let settings = target.build_settings(objects, configuration, source_root.as_ref())?;
+        Self::from_settings(objects, target, &settings, source_root)
+    }
+
+    /// Generate compile commands for target's sources with the given build settings.
+    ///
+    /// Useful to override settings before generation, e.g. setting `SDKROOT` to the full sdk
+    /// path as returned by `xcrun --show-sdk-path`.
+    pub fn from_settings<P: AsRef<Path>>(
+        objects: &PBXObjectCollection,
+        target: &PBXTarget,
+        settings: &XCBuildSettings,
+        source_root: P,
+    ) -> Result<Self> {
+        let directory = source_root.as_ref().to_path_buf();
+        let mut sources = vec![];
+
+        for build_file in target
+            .build_phases
+            .iter()
+            .filter(|phase| phase.is_sources())
+            .flat_map(|phase| phase.files.iter())
+        {
+            let file = match build_file.file.as_ref() {
+                Some(file) => file,
+                None => continue,
+            };
+
+            let path = match file.full_path(objects, &directory) {
+                Ok(path) => path,
+                Err(_err) => {
+                    #[cfg(feature = "with_tracing")]
+                    tracing::debug!("Skipping {:?}: {_err}", file.id);
+                    continue;
+                }
+            };
+
+            let language = match SourceLanguage::from_path(&path) {
+                Some(language) => language,
+                None => continue,
+            };
+
+            let compiler_flags = build_file
+                .settings
+                .and_then(PBXValue::as_object)
+                .and_then(|s| s.get_string("COMPILER_FLAGS"))
+                .map(|flags| settings.expand(flags))
+                .map(|flags| crate::pbxproj::split_setting(&flags))
+                .unwrap_or_default();
+
+            sources.push((path, language, compiler_flags));
+        }
+
+        let swift_files = sources
+            .iter()
+            .filter(|(_, language, _)| language == &SourceLanguage::Swift)
+            .map(|(path, _, _)| path.to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        let swift_arguments = swift_arguments(settings);
+
+        let commands = sources
+            .into_iter()
+            .map(|(file, language, compiler_flags)| {
+                let mut arguments = if language == SourceLanguage::Swift {
+                    swift_arguments.clone()
+                } else {
+                    clang_arguments(settings, language)
+                };
+
+                arguments.extend(compiler_flags);
+
+                if language == SourceLanguage::Swift {
+                    arguments.extend(swift_files.iter().cloned());
+                } else {
+                    arguments.extend(["-c".into(), file.to_string_lossy().to_string()]);
+                }
+
+                CompileCommand {
+                    directory: directory.clone(),
+                    file,
+                    arguments,
+                }
+            })
+            .collect();
+
+        Ok(Self(commands))
+    }
+
+    /// Serialize compile commands to `compile_commands.json` format
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Target triple built from platform (derived from `SDKROOT`) and deployment target.
+///
+/// Simulator sdks, i.e. `iphonesimulator`, add a `-simulator` environment to the triple.
+fn target_triple(settings: &XCBuildSettings) -> Option<String> {
+    let sdkroot = settings.get("SDKROOT")?;
+    let sdk_name = Path::new(sdkroot)
+        .file_name()?
+        .to_str()?
+        .to_lowercase()
+        .trim_end_matches(".sdk")
+        .trim_end_matches(|c: char| c.is_ascii_digit() || c == '.')
+        .to_string();
+    let (sdk_name, environment) = match sdk_name.strip_suffix("simulator") {
+        Some(device) => (format!("{device}os"), "-simulator"),
+        None => (sdk_name, ""),
+    };
+
+    let (os, deployment_target_key) = match PBXTargetPlatform::from_sdk_root(&sdk_name) {
+        PBXTargetPlatform::IOS => ("ios", "IPHONEOS_DEPLOYMENT_TARGET"),
+        PBXTargetPlatform::MacOS => ("macosx", "MACOSX_DEPLOYMENT_TARGET"),
+        PBXTargetPlatform::TvOS => ("tvos", "TVOS_DEPLOYMENT_TARGET"),
+        PBXTargetPlatform::WatchOS => ("watchos", "WATCHOS_DEPLOYMENT_TARGET"),
+        PBXTargetPlatform::XrOS => ("xros", "XROS_DEPLOYMENT_TARGET"),
+        PBXTargetPlatform::Unknown => return None,
+    };
+
+    let arch = settings
+        .get_list("ARCHS")
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| "arm64".into());
+    let version = settings
+        .get(deployment_target_key)
+        .map(String::as_str)
+        .unwrap_or_default();
+
+    Some(format!("{arch}-apple-{os}{version}{environment}"))
+}
+
+/// `-I` and `-F` arguments from search paths build settings
+fn search_paths_arguments(settings: &XCBuildSettings, include_keys: &[&str]) -> Vec<String> {
+    let strip_recursive = |path: String| path.trim_end_matches("/**").to_string();
+
+    include_keys
+        .iter()
+        .flat_map(|key| settings.get_list(key))
+        .map(strip_recursive)
+        .map(|path| format!("-I{path}"))
+        .chain(
+            settings
+                .get_list("FRAMEWORK_SEARCH_PATHS")
+                .into_iter()
+                .map(strip_recursive)
+                .map(|path| format!("-F{path}")),
+        )
+        .collect()
+}
+
+fn swift_arguments(settings: &XCBuildSettings) -> Vec<String> {
+    let mut arguments = vec!["swiftc".to_string()];
+
+    if let Some(sdkroot) = settings.get("SDKROOT") {
+        arguments.extend(["-sdk".into(), sdkroot.clone()]);
+    }
+
+    if let Some(triple) = target_triple(settings) {
+        arguments.extend(["-target".into(), triple]);
+    }
+
+    let module_name = settings
+        .get("PRODUCT_MODULE_NAME")
+        .cloned()
+        .unwrap_or_else(|| settings.expand("$(PRODUCT_NAME:c99extidentifier)"));
+    arguments.extend(["-module-name".into(), module_name]);
+
+    if let Some(version) = settings.get("SWIFT_VERSION") {
+        let major = version.split('.').next().unwrap_or(version);
+        arguments.extend(["-swift-version".into(), major.into()]);
+    }
+
+    arguments.extend(search_paths_arguments(
+        settings,
+        &[
+            "SWIFT_INCLUDE_PATHS",
+            "HEADER_SEARCH_PATHS",
+            "USER_HEADER_SEARCH_PATHS",
+        ],
+    ));
+
+    arguments.extend(
+        settings
+            .get_list("SWIFT_ACTIVE_COMPILATION_CONDITIONS")
+            .into_iter()
+            .map(|condition| format!("-D{condition}")),
+    );
+
+    arguments.extend(
+        settings
+            .get_list("GCC_PREPROCESSOR_DEFINITIONS")
+            .into_iter()
+            .flat_map(|definition| ["-Xcc".into(), format!("-D{definition}")]),
+    );
+
+    arguments.extend(settings.get_list("OTHER_SWIFT_FLAGS"));
+    arguments
+}
+
+fn clang_arguments(settings: &XCBuildSettings, language: SourceLanguage) -> Vec<String> {
+    let mut arguments = vec![
+        "clang".to_string(),
+        "-x".into(),
+        language.clang_language().into(),
+    ];
+
+    if let Some(sdkroot) = settings.get("SDKROOT") {
+        arguments.extend(["-isysroot".into(), sdkroot.clone()]);
+    }
+
+    if let Some(triple) = target_triple(settings) {
+        arguments.extend(["-target".into(), triple]);
+    }
+
+    let standard = if language.is_cpp() {
+        settings.get("CLANG_CXX_LANGUAGE_STANDARD")
+    } else {
+        settings.get("GCC_C_LANGUAGE_STANDARD")
+    };
+
+    if let Some(standard) = standard {
+        arguments.push(format!("-std={standard}"));
+    }
+
+    if settings.get_bool("CLANG_ENABLE_MODULES") {
+        arguments.push("-fmodules".into());
+    }
+
+    if language.is_objc() && settings.get_bool("CLANG_ENABLE_OBJC_ARC") {
+        arguments.push("-fobjc-arc".into());
+    }
+
+    arguments.extend(search_paths_arguments(
+        settings,
+        &["HEADER_SEARCH_PATHS", "USER_HEADER_SEARCH_PATHS"],
+    ));
+
+    arguments.extend(
+        settings
+            .get_list("GCC_PREPROCESSOR_DEFINITIONS")
+            .into_iter()
+            .map(|definition| format!("-D{definition}")),
+    );
+
+    arguments.extend(settings.get_list("OTHER_CFLAGS"));
+    if language.is_cpp() {
+        arguments.extend(settings.get_list("OTHER_CPLUSPLUSFLAGS"));
+    }
+
+    arguments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbxproj::test_demo_file;
+
+    #[test]
+    fn swift_target() {
+        let project = test_demo_file!(demo1);
+        let target = project.get_target_by_name("Wordle").unwrap();
+        let commands = CompileCommands::new(&project, &target, "Debug", "/root").unwrap();
+
+        assert_eq!(12, commands.len());
+        let command = commands
+            .iter()
+            .find(|c| c.file.ends_with("Source/Views/GuessView.swift"))
+            .unwrap();
+        assert_eq!(command.directory, PathBuf::from("/root"));
+        assert_eq!(
+            command.arguments[..12],
+            [
+                "swiftc",
+                "-sdk",
+                "iphoneos",
+                "-target",
+                "arm64-apple-ios15.0",
+                "-module-name",
+                "Wordle",
+                "-swift-version",
+                "5",
+                "-DDEBUG",
+                "-Xcc",
+                "-DDEBUG=1",
+            ]
+        );
+        // All module sources are passed to swiftc
+        assert_eq!(command.arguments.len(), 12 + 12);
+        assert!(commands
+            .to_json()
+            .unwrap()
+            .contains("\"directory\": \"/root\""));
+    }
+
+    #[test]
+    fn simulator_target() {
+        let project = test_demo_file!(demo1);
+        let target = project.get_target_by_name("Wordle").unwrap();
+        let mut settings = target.build_settings(&project, "Debug", "/root").unwrap();
+        settings.insert(
+            "SDKROOT",
+            "/Applications/Xcode.app/Contents/Developer/Platforms/iPhoneSimulator.platform/\
+            Developer/SDKs/iPhoneSimulator17.0.sdk",
+        );
+        let commands =
+            CompileCommands::from_settings(&project, &target, &settings, "/root").unwrap();
+
+        assert!(commands.iter().all(|command| command
+            .arguments
+            .windows(2)
+            .any(|pair| pair == ["-target", "arm64-apple-ios15.0-simulator"])));
+    }
+
+    #[test]
+    fn objc_target_with_compiler_flags() {
+        let project = test_demo_file!(demo8);
+        let target = project.get_target_by_name("test").unwrap();
+        let commands = CompileCommands::new(&project, &target, "Debug", "/root").unwrap();
+
+        assert_eq!(3, commands.len());
+        let command = commands
+            .iter()
+            .find(|c| c.file == Path::new("/root/test/ViewController.m"))
+            .unwrap();
+        assert_eq!(
+            command.arguments,
+            [
+                "clang",
+                "-x",
+                "objective-c",
+                "-isysroot",
+                "iphoneos",
+                "-target",
+                "arm64-apple-ios9.3",
+                "-std=gnu99",
+                "-fmodules",
+                "-fobjc-arc",
+                "-DDEBUG=1",
+                "-flag",
+                "-flag2",
+                "-c",
+                "/root/test/ViewController.m",
+            ]
+        );
+        assert!(commands
+            .iter()
+            .any(|c| c.file == Path::new("/root/test/main.m")));
+    }
+}
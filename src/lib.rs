@@ -1,4 +1,5 @@
 #![allow(dead_code)]
+#![allow(clippy::too_many_arguments)]
 #![deny(future_incompatible)]
 #![deny(nonstandard_style)]
 #![deny(missing_docs)]
@@ -8,12 +9,19 @@
 use anyhow::Result;
 use pbxproj::PBXRootObject;
 use std::path::{Path, PathBuf};
+use tap::Pipe;
 
+#[cfg(feature = "bsp")]
+pub mod bsp;
+mod compile_commands;
 mod macros;
+mod package_resolved;
 pub mod pbxproj;
 mod scheme;
 pub mod xcode;
-pub use scheme::XCScheme;
+pub use compile_commands::{CompileCommand, CompileCommands};
+pub use package_resolved::*;
+pub use scheme::{XCBuildableReference, XCScheme};
 
 /// Main presentation of XCodeProject
 #[derive(Debug, Default, derive_deref_rs::Deref)]
@@ -23,11 +31,15 @@ pub struct XCodeProject {
     #[deref]
     pbxproj: PBXRootObject,
     schemes: Vec<XCScheme>,
+    package_resolved: Option<XCPackageResolved>,
 }
 
 impl XCodeProject {
     // /xcshareddata/xcschemes
     /// Create new XCodeProject object from xcodeproj_folder
+    ///
+    /// A `Package.resolved` that can't be read or parsed, i.e. one written by a newer Xcode, is
+    /// skipped with a warning, leaving the project without resolved packages.
     pub fn new<P: AsRef<Path>>(xcodeproj_folder: P) -> Result<Self> {
         let xcodeproj_folder = xcodeproj_folder.as_ref();
         let name = xcodeproj_folder
@@ -50,12 +62,24 @@ impl XCodeProject {
         }
 
         let pbxproj = PBXRootObject::try_from(xcodeproj_folder.join("project.pbxproj"))?;
+        let package_resolved = xcodeproj_folder
+            .join("project.xcworkspace")
+            .join("xcshareddata")
+            .join("swiftpm")
+            .join("Package.resolved")
+            .pipe(XCPackageResolved::read)
+            .unwrap_or_else(|_err| {
+                #[cfg(feature = "with_tracing")]
+                tracing::warn!("Skipping Package.resolved: {_err:#}");
+                None
+            });
 
         Ok(Self {
             name,
             root,
             pbxproj,
             schemes,
+            package_resolved,
         })
     }
 
@@ -92,4 +116,82 @@ impl XCodeProject {
     pub fn schemes(&self) -> &[XCScheme] {
         self.schemes.as_ref()
     }
+
+    /// Remove target with a given name and objects that exclusively belong to it, returning
+    /// schemes that still reference it and need to be updated.
+    pub fn remove_target_by_name(&mut self, name: &str) -> Result<Vec<&XCScheme>> {
+        let id = self
+            .get_target_by_name(name)
+            .ok_or_else(|| anyhow::anyhow!("No target found with {name:?}"))?
+            .id
+            .clone();
+        self.pbxproj.objects_mut().remove_target(&id)?;
+
+        Ok(self
+            .schemes
+            .iter()
+            .filter(|scheme| scheme.references_target(&id, name))
+            .collect())
+    }
+
+    /// Get swift package versions resolved by Xcode, if any
+    pub fn package_resolved(&self) -> Option<&XCPackageResolved> {
+        self.package_resolved.as_ref()
+    }
+
+    /// Get project swift package references joined with their resolved pins
+    pub fn resolved_packages(&self) -> Vec<XCResolvedPackage<'_>> {
+        let packages = self.root_project().packages;
+        match self.package_resolved.as_ref() {
+            Some(resolved) => resolved.resolve(packages),
+            None => packages
+                .into_iter()
+                .map(|package| XCResolvedPackage { package, pin: None })
+                .collect(),
+        }
+    }
+
+    /// Get the directory build settings and file paths are resolved against (`SRCROOT`).
+    pub fn source_root(&self) -> PathBuf {
+        self.root.join(self.root_project().project_dir_path)
+    }
+
+    /// Generate `compile_commands.json` entries for a target and configuration
+    pub fn compile_commands(
+        &self,
+        target_name: &str,
+        configuration: &str,
+    ) -> Result<CompileCommands> {
+        let target = self
+            .get_target_by_name(target_name)
+            .ok_or_else(|| anyhow::anyhow!("No target found with {target_name:?}"))?;
+
+        CompileCommands::new(&self.pbxproj, &target, configuration, self.source_root())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skip_malformed_package_resolved() {
+        let root = tempfile::tempdir().unwrap();
+        let xcodeproj_folder = root.path().join("Wordle.xcodeproj");
+        let swiftpm = xcodeproj_folder.join("project.xcworkspace/xcshareddata/swiftpm");
+        std::fs::create_dir_all(&swiftpm).unwrap();
+        std::fs::copy(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/samples/demo1.pbxproj"),
+            xcodeproj_folder.join("project.pbxproj"),
+        )
+        .unwrap();
+        std::fs::write(swiftpm.join("Package.resolved"), r#"{"version": 42"#).unwrap();
+
+        let project = XCodeProject::new(&xcodeproj_folder).unwrap();
+        assert_eq!(project.name(), "Wordle");
+        assert!(project.package_resolved().is_none());
+        let packages = project.resolved_packages();
+        assert_eq!(packages.len(), 1);
+        assert!(packages[0].pin.is_none());
+    }
 }
@@ -0,0 +1,341 @@
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::pbxproj::{XCRemoteSwiftPackageReference, XCSemanticVersion, XCVersionRequirement};
+
+/// Swift package versions resolved by Xcode (`Package.resolved`)
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XCPackageResolved {
+    /// File format version, 1 to 3
+    pub version: u64,
+    /// Hash of package dependencies pins were resolved from (v3 only)
+    pub origin_hash: Option<String>,
+    /// Resolved packages
+    pub pins: Vec<XCPackagePin>,
+}
+
+/// Resolved package
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct XCPackagePin {
+    /// Package identity, i.e. `swift-log` (package name in v1)
+    pub identity: String,
+    /// Package kind, i.e. `remoteSourceControl` (missing in v1)
+    pub kind: Option<String>,
+    /// Repository url
+    pub location: String,
+    /// Resolved state
+    pub state: XCPackagePinState,
+}
+
+/// Resolved package state
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct XCPackagePinState {
+    /// Resolved branch
+    pub branch: Option<String>,
+    /// Resolved revision
+    pub revision: Option<String>,
+    /// Resolved version
+    pub version: Option<String>,
+}
+
+/// [`XCRemoteSwiftPackageReference`] with its [`XCPackagePin`] if resolved
+#[derive(Debug)]
+pub struct XCResolvedPackage<'a> {
+    /// Project package reference
+    pub package: XCRemoteSwiftPackageReference<'a>,
+    /// Matching pin
+    pub pin: Option<&'a XCPackagePin>,
+}
+
+#[derive(Deserialize)]
+struct FormatVersion {
+    version: u64,
+}
+
+#[derive(Deserialize)]
+struct V1 {
+    object: V1Object,
+}
+
+#[derive(Deserialize)]
+struct V1Object {
+    pins: Vec<V1Pin>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct V1Pin {
+    package: String,
+    #[serde(rename = "repositoryURL")]
+    repository_url: String,
+    state: XCPackagePinState,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct V2 {
+    origin_hash: Option<String>,
+    pins: Vec<XCPackagePin>,
+}
+
+impl XCPackageResolved {
+    /// Read and parse Package.resolved content
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Read and parse Package.resolved if it exists.
+    ///
+    /// Unlike [`XCPackageResolved::new`], a missing file isn't an error, but one that can't
+    /// be read or parsed still is.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Option<Self>> {
+        let path = path.as_ref();
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {path:?}")),
+        };
+        Self::from_json(&content)
+            .with_context(|| format!("Failed to parse {path:?}"))
+            .map(Some)
+    }
+
+    /// Parse Package.resolved content of any supported version
+    pub fn from_json(content: &str) -> Result<Self> {
+        let version = serde_json::from_str::<FormatVersion>(content)?.version;
+        match version {
+            1 => {
+                let pins = serde_json::from_str::<V1>(content)?
+                    .object
+                    .pins
+                    .into_iter()
+                    .map(|pin| XCPackagePin {
+                        identity: pin.package,
+                        kind: None,
+                        location: pin.repository_url,
+                        state: pin.state,
+                    })
+                    .collect();
+                Ok(Self {
+                    version,
+                    origin_hash: None,
+                    pins,
+                })
+            }
+            2 | 3 => {
+                let resolved = serde_json::from_str::<V2>(content)?;
+                Ok(Self {
+                    version,
+                    origin_hash: resolved.origin_hash,
+                    pins: resolved.pins,
+                })
+            }
+            version => bail!("Unsupported Package.resolved version {version}"),
+        }
+    }
+
+    /// Get pin with a given repository url, compared after normalization
+    pub fn get_pin(&self, repository_url: &str) -> Option<&XCPackagePin> {
+        let repository_url = normalize_repository_url(repository_url);
+        self.pins
+            .iter()
+            .find(|pin| normalize_repository_url(&pin.location) == repository_url)
+    }
+
+    /// Join package references with their pins
+    pub fn resolve<'a>(
+        &'a self,
+        packages: Vec<XCRemoteSwiftPackageReference<'a>>,
+    ) -> Vec<XCResolvedPackage<'a>> {
+        packages
+            .into_iter()
+            .map(|package| XCResolvedPackage {
+                pin: package.repository_url.and_then(|url| self.get_pin(url)),
+                package,
+            })
+            .collect()
+    }
+}
+
+impl<'a> XCResolvedPackage<'a> {
+    /// Pinned version, or else pinned revision
+    pub fn pinned(&self) -> Option<&'a str> {
+        let state = &self.pin?.state;
+        state.version.as_deref().or(state.revision.as_deref())
+    }
+
+    /// Whether pin satisfies package version requirement.
+    ///
+    /// None if package isn't resolved, has no requirement, or pin lacks the state the
+    /// requirement is about, i.e. a version for a version range.
+    pub fn is_satisfied(&self) -> Option<bool> {
+        let state = &self.pin?.state;
+        match self.package.version_requirement.as_ref()? {
+            XCVersionRequirement::Branch(branch) => Some(state.branch.as_ref()? == branch),
+            XCVersionRequirement::Revision(revision) => Some(state.revision.as_ref()? == revision),
+            requirement => Some(requirement.matches(&state.version.as_ref()?.parse().ok()?)),
+        }
+    }
+
+    /// Get newest of given tags allowed by package version requirement and newer than the
+    /// pinned version, i.e. an available update.
+    pub fn update_available<'t, I>(&self, tags: I) -> Option<&'t str>
+    where
+        I: IntoIterator<Item = &'t str>,
+    {
+        let pinned = self.pin?.state.version.as_ref()?;
+        let pinned = pinned.parse::<XCSemanticVersion>().ok()?;
+        self.package
+            .version_requirement
+            .as_ref()?
+            .newest_matching(tags)
+            .filter(|tag| tag.parse::<XCSemanticVersion>().ok() > Some(pinned))
+    }
+}
+
+/// Normalize repository url so that different spellings of the same repository are equal,
+/// i.e. `git@github.com:apple/swift-log.git` and `https://github.com/Apple/swift-log` are both
+/// `github.com/apple/swift-log`
+pub fn normalize_repository_url(url: &str) -> String {
+    let url = url.trim().to_lowercase();
+    let (url, is_scp) = match url.split_once("://") {
+        Some((_, rest)) => (rest.to_string(), false),
+        None => (url, true),
+    };
+    let url = match url.split_once('@') {
+        Some((_, rest)) => rest.to_string(),
+        None => url,
+    };
+    let url = if is_scp {
+        url.replacen(':', "/", 1)
+    } else {
+        url
+    };
+    let url = url.trim_end_matches('/');
+    url.strip_suffix(".git").unwrap_or(url).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbxproj::test_demo_file;
+
+    fn read(name: &str) -> XCPackageResolved {
+        let root = env!("CARGO_MANIFEST_DIR");
+        XCPackageResolved::new(format!("{root}/tests/resolved/{name}.resolved")).unwrap()
+    }
+
+    #[test]
+    fn formats() {
+        let v1 = read("v1");
+        assert_eq!(v1.version, 1);
+        assert_eq!(v1.pins.len(), 2);
+        assert_eq!(v1.pins[1].identity, "SwiftyJSON");
+        assert_eq!(v1.pins[1].state.branch.as_deref(), Some("master"));
+
+        let v2 = read("v2");
+        assert_eq!(v2.pins[0].kind.as_deref(), Some("remoteSourceControl"));
+        assert_eq!(v2.pins[0].state.version.as_deref(), Some("1.4.4"));
+
+        let v3 = read("v3");
+        assert_eq!(v3.version, 3);
+        assert!(v3.origin_hash.is_some());
+
+        let error = XCPackageResolved::from_json(r#"{"version": 4, "pins": []}"#).unwrap_err();
+        assert_eq!(error.to_string(), "Unsupported Package.resolved version 4");
+    }
+
+    #[test]
+    fn read_optional() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join("Package.resolved");
+        assert!(XCPackageResolved::read(&path).unwrap().is_none());
+
+        std::fs::write(&path, r#"{"version": 2, "pins": [}"#).unwrap();
+        let error = XCPackageResolved::read(&path).unwrap_err();
+        assert!(error.to_string().starts_with("Failed to parse"));
+
+        std::fs::copy(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/resolved/v2.resolved"),
+            &path,
+        )
+        .unwrap();
+        assert!(XCPackageResolved::read(&path).unwrap().is_some());
+    }
+
+    #[test]
+    fn normalize_urls() {
+        let expected = "github.com/apple/swift-log";
+        for url in [
+            "https://github.com/apple/swift-log.git",
+            "https://github.com/Apple/swift-log/",
+            "git@github.com:apple/swift-log.git",
+            "ssh://git@github.com/apple/swift-log",
+        ] {
+            assert_eq!(normalize_repository_url(url), expected, "{url}");
+        }
+    }
+
+    #[test]
+    fn resolve_packages() {
+        let project = test_demo_file!(demo1);
+        for (name, pinned, satisfied) in [
+            ("v1", "1.4.2", true),
+            ("v2", "1.4.4", false),
+            ("v3", "1.4.2", true),
+        ] {
+            let resolved = read(name);
+            let packages = resolved.resolve(project.swift_package_references());
+            assert_eq!(packages.len(), 1);
+            assert_eq!(packages[0].pinned(), Some(pinned), "{name}");
+            assert_eq!(packages[0].is_satisfied(), Some(satisfied), "{name}");
+        }
+    }
+
+    #[test]
+    fn version_requirements() {
+        use XCVersionRequirement::*;
+
+        let satisfies = |requirement: XCVersionRequirement, version: &str| {
+            let pin = XCPackagePin {
+                state: XCPackagePinState {
+                    version: Some(version.into()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            XCResolvedPackage {
+                package: XCRemoteSwiftPackageReference::new("id".into(), None, Some(requirement)),
+                pin: Some(&pin),
+            }
+            .is_satisfied()
+            .unwrap()
+        };
+
+        assert!(satisfies(UpToNextMajorVersion("1.2.0".into()), "1.9.1"));
+        assert!(!satisfies(UpToNextMajorVersion("1.2.0".into()), "2.0.0"));
+        assert!(!satisfies(UpToNextMajorVersion("1.2.0".into()), "1.1.0"));
+        assert!(satisfies(UpToNextMinorVersion("1.2.0".into()), "1.2.5"));
+        assert!(!satisfies(UpToNextMinorVersion("1.2.0".into()), "1.3.0"));
+        assert!(satisfies(Range("1.0.0".into(), "2.0.0".into()), "1.5.0"));
+        assert!(!satisfies(Range("1.0.0".into(), "2.0.0".into()), "2.0.0"));
+        assert!(satisfies(Exact("1.4".into()), "1.4.0"));
+    }
+
+    #[test]
+    fn update_available() {
+        let project = test_demo_file!(demo1);
+        let resolved = read("v1");
+        let mut packages = resolved.resolve(project.swift_package_references());
+        let tags = ["1.4.0", "1.4.2", "1.4.4", "1.5.0"];
+        // Pinned with an exact requirement
+        assert_eq!(packages[0].update_available(tags), None);
+
+        packages[0].package.version_requirement =
+            Some(XCVersionRequirement::UpToNextMinorVersion("1.4.0".into()));
+        assert_eq!(packages[0].update_available(tags), Some("1.4.4"));
+    }
+}
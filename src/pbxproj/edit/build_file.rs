@@ -0,0 +1,54 @@
+use super::*;
+
+impl PBXRootObject {
+    /// Update typed settings of a build file with a given id, i.e. to make a header public,
+    /// weakly link a framework or compile a file with `-fno-objc-arc`.
+    pub fn update_build_file_settings<R>(
+        &mut self,
+        id: &str,
+        f: impl FnOnce(&mut PBXBuildFileSettings) -> R,
+    ) -> Result<R> {
+        let mut build_file = self.objects.get_owned::<PBXBuildFileData>(id)?;
+        let mut settings = build_file.typed_settings();
+        let result = f(&mut settings);
+        build_file.set_typed_settings(settings);
+        self.objects.insert(id.to_string(), build_file.into());
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbxproj::test_demo_file;
+
+    #[test]
+    fn update_build_file_settings() {
+        let mut project = test_demo_file!(demo8);
+        project
+            .update_build_file_settings("4FB900671D5B314C0074397B", |settings| {
+                settings.set_compiler_flags(["-fno-objc-arc"]);
+                settings.set_attribute(PBXBuildFileAttribute::Weak, true);
+            })
+            .unwrap();
+        project
+            .update_build_file_settings("4FB900851D5B317C0074397B", |settings| {
+                settings.set_attribute(PBXBuildFileAttribute::Weak, false)
+            })
+            .unwrap();
+
+        let object = project.get_object("4FB900671D5B314C0074397B").unwrap();
+        let settings = object.get_object("settings").unwrap();
+        assert_eq!(settings.get_str("COMPILER_FLAGS"), Some("-fno-objc-arc"));
+        assert_eq!(
+            settings.get_vec("ATTRIBUTES").unwrap().as_vec_strings(),
+            vec!["Weak"]
+        );
+
+        let object = project.get_object("4FB900851D5B317C0074397B").unwrap();
+        assert!(object.get_value("settings").is_none());
+        assert!(project
+            .update_build_file_settings("missing", |_| ())
+            .is_err());
+    }
+}
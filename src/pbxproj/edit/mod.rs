@@ -0,0 +1,92 @@
+//! Editing of [`PBXRootObject`]: creating, duplicating and removing objects.
+//!
+//! Edits work on raw objects and owned counterparts, so that borrowed views are only resolved
+//! once the collection is consistent again.
+mod build_file;
+mod package;
+mod target;
+
+use crate::pbxproj::*;
+use anyhow::{anyhow, Result};
+use std::collections::HashSet;
+
+impl PBXRootObject {
+    /// Get raw root PBXProject object
+    fn root_project_object(&self) -> Result<&PBXHashMap> {
+        self.objects
+            .get_object(&self.root_object_reference)
+            .ok_or_else(|| anyhow!("Root project {:?} not found", self.root_object_reference))
+    }
+
+    /// Update raw root PBXProject object in place
+    fn update_root_project<R>(&mut self, f: impl FnOnce(&mut PBXHashMap) -> R) -> Result<R> {
+        let id = self.root_object_reference.clone();
+        self.objects
+            .update(&id, f)
+            .ok_or_else(|| anyhow!("Root project {id:?} not found"))
+    }
+}
+
+impl PBXObjectCollection {
+    /// Remove objects with given ids, dropping their ids from lists of remaining objects,
+    /// i.e. group `children` or build phase `files`.
+    fn remove_linked(&mut self, ids: &HashSet<String>) {
+        self.remove_all(ids);
+
+        let is_removed = |value: &PBXValue| value.as_string().is_some_and(|id| ids.contains(id));
+        let linked = self
+            .iter()
+            .filter(|(_, object)| {
+                object
+                    .values()
+                    .flat_map(PBXValue::as_vec)
+                    .any(|vec| vec.iter().any(is_removed))
+            })
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        for id in linked {
+            self.update(&id, |object| {
+                for vec in object.0.values_mut().flat_map(PBXValue::as_vec_mut) {
+                    vec.0.retain(|value| !is_removed(value));
+                }
+            });
+        }
+    }
+
+    /// Whether any object value references a given id
+    fn is_referenced(&self, id: &str) -> bool {
+        fn references(value: &PBXValue, id: &str) -> bool {
+            match value {
+                PBXValue::String(value) => value == id,
+                PBXValue::Object(object) => object.values().any(|v| references(v, id)),
+                PBXValue::Vec(vec) => vec.iter().any(|v| references(v, id)),
+                _ => false,
+            }
+        }
+
+        self.values()
+            .any(|object| object.values().any(|value| references(value, id)))
+    }
+}
+
+/// Get mutable vec with a given key, inserting an empty one if missing
+fn vec_entry<'a>(object: &'a mut PBXHashMap, key: &str) -> &'a mut PBXVec {
+    if object.get_vec(key).is_none() {
+        object.insert_value(key, PBXValue::Vec(PBXVec::default()));
+    }
+    object
+        .get_value_mut(key)
+        .and_then(PBXValue::as_vec_mut)
+        .unwrap()
+}
+
+/// Get mutable object with a given key, inserting an empty one if missing
+fn object_entry<'a>(object: &'a mut PBXHashMap, key: &str) -> &'a mut PBXHashMap {
+    if object.get_object(key).is_none() {
+        object.insert_value(key, PBXValue::Object(PBXHashMap::default()));
+    }
+    object
+        .get_value_mut(key)
+        .and_then(PBXValue::as_object_mut)
+        .unwrap()
+}
@@ -0,0 +1,341 @@
+use super::*;
+use crate::normalize_repository_url;
+use anyhow::bail;
+use std::collections::HashSet;
+
+impl PBXRootObject {
+    /// Add product of a remote swift package to a target, returning the package reference id.
+    ///
+    /// The package reference is created in `packageReferences` of the root project, or reused if
+    /// one with the same repository url, see [`normalize_repository_url`], exists. The product
+    /// is added to the target's `packageProductDependencies` and linked in its Frameworks build
+    /// phase. Errors if the existing package has a different version requirement, which is
+    /// changed with [`PBXRootObject::set_swift_package_requirement`] instead, or if the target
+    /// already depends on the product.
+    pub fn add_swift_package(
+        &mut self,
+        repository_url: &str,
+        product_name: &str,
+        version_requirement: XCVersionRequirement,
+        target_name: &str,
+    ) -> Result<String> {
+        let target_id = self.target_id(target_name)?;
+        let target = self.objects.get_owned::<PBXTargetData>(&target_id)?;
+        let package = self.swift_package_reference_id(repository_url)?;
+        let product = package
+            .as_ref()
+            .and_then(|package| self.package_product(&target, product_name, package));
+
+        let package = match package {
+            Some(id) => {
+                let package = self
+                    .objects
+                    .get_owned::<XCRemoteSwiftPackageReferenceData>(&id)?;
+                if package.requirement.as_ref() != Some(&version_requirement) {
+                    bail!(
+                        "{repository_url:?} is added already with {:?}, reference key: {id:?}",
+                        package.requirement
+                    )
+                }
+                if product.is_some() {
+                    bail!("{product_name:?} with {version_requirement:?} is added already, reference key: {id:?}")
+                }
+                id
+            }
+            None => {
+                let id = self.objects.push(XCRemoteSwiftPackageReferenceData::new(
+                    repository_url,
+                    version_requirement,
+                ));
+                self.update_root_project(|project| {
+                    vec_entry(project, "packageReferences")
+                        .0
+                        .push(id.clone().into())
+                })?;
+                id
+            }
+        };
+
+        if product.is_none() {
+            let frameworks = target
+                .build_phases
+                .iter()
+                .find(|id| {
+                    self.objects.get_object(id).and_then(|p| p.get_kind("isa"))
+                        == Some(&PBXObjectKind::PBXBuildPhase(PBXBuildPhaseKind::Frameworks))
+                })
+                .cloned()
+                .ok_or_else(|| anyhow!("Frameworks build phase not found for {target_name:?}"))?;
+
+            let product = self.objects.push(XCSwiftPackageProductDependencyData::new(
+                product_name,
+                package.as_str(),
+            ));
+            let build_file = self.objects.push(PBXBuildFileData {
+                product_ref: Some(product.clone()),
+                ..Default::default()
+            });
+            self.objects.update(&frameworks, |phase| {
+                vec_entry(phase, "files").0.push(build_file.into())
+            });
+            self.objects.update(&target_id, |target| {
+                vec_entry(target, "packageProductDependencies")
+                    .0
+                    .push(product.into())
+            });
+        }
+
+        Ok(package)
+    }
+
+    /// Remove product of a remote swift package from a target.
+    ///
+    /// Removes the product dependency along with build files and target dependencies using it.
+    /// The package reference itself is removed once no other target uses any of its products.
+    pub fn remove_swift_package(
+        &mut self,
+        repository_url: &str,
+        product_name: &str,
+        target_name: &str,
+    ) -> Result<()> {
+        let target_id = self.target_id(target_name)?;
+        let target = self.objects.get_owned::<PBXTargetData>(&target_id)?;
+        let package = self
+            .swift_package_reference_id(repository_url)?
+            .ok_or_else(|| anyhow!("No swift package found with {repository_url:?}"))?;
+        let product = self
+            .package_product(&target, product_name, &package)
+            .ok_or_else(|| anyhow!("{target_name:?} doesn't depend on {product_name:?}"))?;
+
+        let mut removed = self
+            .objects
+            .iter()
+            .filter(|(_, object)| object.get_str("productRef") == Some(product.as_str()))
+            .map(|(id, _)| id.clone())
+            .collect::<HashSet<_>>();
+        removed.insert(product);
+        self.objects.remove_linked(&removed);
+
+        let is_used = self.objects.values().any(|object| {
+            object.get_kind("isa") == Some(&PBXObjectKind::XCSwiftPackageProductDependency)
+                && object.get_str("package") == Some(package.as_str())
+        });
+        if !is_used {
+            self.objects.remove_linked(&HashSet::from([package]));
+        }
+
+        Ok(())
+    }
+
+    /// Set version requirement of a remote swift package with a given repository url
+    pub fn set_swift_package_requirement(
+        &mut self,
+        repository_url: &str,
+        version_requirement: XCVersionRequirement,
+    ) -> Result<()> {
+        let package = self
+            .swift_package_reference_id(repository_url)?
+            .ok_or_else(|| anyhow!("No swift package found with {repository_url:?}"))?;
+        self.objects.update(&package, |package| {
+            package.insert_value("requirement", version_requirement)
+        });
+        Ok(())
+    }
+
+    /// Get id of target with a given name
+    fn target_id(&self, name: &str) -> Result<String> {
+        self.objects
+            .get_target_by_name(name)
+            .map(|target| target.id)
+            .ok_or_else(|| anyhow!("No target found with {name:?}"))
+    }
+
+    /// Get id of root project package reference with a given repository url, see
+    /// [`normalize_repository_url`]
+    fn swift_package_reference_id(&self, repository_url: &str) -> Result<Option<String>> {
+        let repository_url = normalize_repository_url(repository_url);
+        Ok(self
+            .root_project_object()?
+            .get_vec("packageReferences")
+            .map(|references| references.as_vec_strings())
+            .unwrap_or_default()
+            .into_iter()
+            .find(|id| {
+                self.objects
+                    .get_object(id)
+                    .and_then(|p| p.get_str("repositoryURL"))
+                    .is_some_and(|url| normalize_repository_url(url) == repository_url)
+            })
+            .cloned())
+    }
+
+    /// Get id of target product dependency with a given name and package reference id
+    fn package_product(
+        &self,
+        target: &PBXTargetData,
+        product_name: &str,
+        package: &str,
+    ) -> Option<String> {
+        target
+            .package_product_dependencies
+            .iter()
+            .find(|id| {
+                self.objects.get_object(id).is_some_and(|product| {
+                    product.get_str("productName") == Some(product_name)
+                        && product.get_str("package") == Some(package)
+                })
+            })
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbxproj::test_demo_file;
+
+    const SWIFT_LOG: &str = "https://github.com/apple/swift-log.git";
+
+    #[test]
+    fn add_swift_package_duplication() {
+        let mut project = test_demo_file!(demo1);
+        let error = project
+            .add_swift_package(
+                SWIFT_LOG,
+                "Logging",
+                XCVersionRequirement::Exact("1.4.2".into()),
+                "Wordle",
+            )
+            .unwrap_err();
+        assert!(error.to_string().contains("added already"));
+    }
+
+    #[test]
+    fn add_swift_package_with_new_version() {
+        let mut project = test_demo_file!(demo1);
+        let build_files = project.build_files().len();
+        let error = project
+            .add_swift_package(
+                SWIFT_LOG,
+                "Logging",
+                XCVersionRequirement::Exact("1.4.3".into()),
+                "Wordle",
+            )
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "{SWIFT_LOG:?} is added already with Some(Exact(\"1.4.2\")), \
+                 reference key: \"C466B20236177CB3939EF1C7\""
+            )
+        );
+
+        let packages = project.root_project().packages;
+        assert_eq!(packages.len(), 1);
+        assert_eq!(
+            packages[0].version_requirement,
+            Some(XCVersionRequirement::Exact("1.4.2".into()))
+        );
+        assert_eq!(project.build_files().len(), build_files);
+    }
+
+    #[test]
+    fn add_swift_package_product_of_existing_package() {
+        let mut project = test_demo_file!(demo1);
+        let id = project
+            .add_swift_package(
+                "git@github.com:Apple/swift-log",
+                "LoggingExtras",
+                XCVersionRequirement::Exact("1.4.2".into()),
+                "Wordle",
+            )
+            .unwrap();
+        assert_eq!(id, "C466B20236177CB3939EF1C7");
+        assert_eq!(project.root_project().packages.len(), 1);
+
+        let target = project.get_target_by_name("Wordle").unwrap();
+        let products = target
+            .package_product_dependencies
+            .iter()
+            .map(|p| p.product_name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(products, vec!["Logging", "LoggingExtras"]);
+
+        project
+            .remove_swift_package("https://github.com/apple/swift-log/", "Logging", "Wordle")
+            .unwrap();
+        assert_eq!(project.root_project().packages.len(), 1);
+    }
+
+    #[test]
+    fn add_swift_package_new_package() {
+        let mut project = test_demo_file!(demo1);
+        let id = project
+            .add_swift_package(
+                "https://github.com/apple/swift-collections.git",
+                "Collections",
+                XCVersionRequirement::UpToNextMajorVersion("1.0.0".into()),
+                "Wordle",
+            )
+            .unwrap();
+
+        let packages = project.root_project().packages;
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[1].id, id);
+
+        let target = project.get_target_by_name("Wordle").unwrap();
+        let product = target
+            .package_product_dependencies
+            .iter()
+            .find(|p| p.product_name == "Collections")
+            .unwrap();
+        assert_eq!(product.package.as_ref().unwrap().id, id);
+
+        let frameworks = target
+            .build_phases
+            .iter()
+            .find(|p| p.kind == PBXBuildPhaseKind::Frameworks)
+            .unwrap();
+        assert!(frameworks
+            .files
+            .iter()
+            .any(|f| f.product.as_ref().map(|p| &p.id) == Some(&product.id)));
+    }
+
+    #[test]
+    fn remove_swift_package() {
+        let mut project = test_demo_file!(demo1);
+        project
+            .remove_swift_package(SWIFT_LOG, "Logging", "Wordle")
+            .unwrap();
+
+        assert!(project.root_project().packages.is_empty());
+        assert!(project.swift_package_product_dependencies().is_empty());
+        assert!(project.get_object("551B59949C78B8B2DF5A3412").is_none());
+        assert!(!project.is_referenced("AB54E6499A232290AC0E2033"));
+
+        let error = project
+            .remove_swift_package(SWIFT_LOG, "Logging", "Wordle")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("No swift package found with {SWIFT_LOG:?}")
+        );
+    }
+
+    #[test]
+    fn set_swift_package_requirement() {
+        let mut project = test_demo_file!(demo1);
+        let requirement = XCVersionRequirement::UpToNextMinorVersion("1.4.0".into());
+        project
+            .set_swift_package_requirement(SWIFT_LOG, requirement.clone())
+            .unwrap();
+        assert_eq!(
+            project.root_project().packages[0].version_requirement,
+            Some(requirement)
+        );
+        assert!(project
+            .set_swift_package_requirement("url", XCVersionRequirement::Exact("1".into()))
+            .is_err());
+    }
+}
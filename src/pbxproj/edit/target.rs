@@ -0,0 +1,855 @@
+use super::*;
+use anyhow::bail;
+use std::collections::{HashMap, HashSet};
+
+/// Objects staged by [`PBXRootObject::duplicate_target`] before insertion
+#[derive(Default)]
+struct PendingObjects(Vec<(String, PBXHashMap)>);
+
+impl PendingObjects {
+    /// Stage object with an id unique within `objects` and staged objects, returning the id
+    fn push<T: Into<PBXHashMap>>(&mut self, objects: &PBXObjectCollection, object: T) -> String {
+        let id = std::iter::repeat_with(|| objects.generate_id())
+            .find(|id| self.0.iter().all(|(staged, _)| staged != id))
+            .unwrap();
+        self.0.push((id.clone(), object.into()));
+        id
+    }
+}
+
+impl PBXRootObject {
+    /// Add a new native target with a given name, product type and platform, returning its id.
+    ///
+    /// Creates a configuration list mirroring the project's configurations, empty Sources,
+    /// Frameworks and Resources build phases and a product reference in the Products group,
+    /// then registers the target in the root project targets and `TargetAttributes`.
+    pub fn add_native_target(
+        &mut self,
+        name: &str,
+        product_type: PBXProductType,
+        platform: PBXTargetPlatform,
+    ) -> Result<String> {
+        if self.objects.get_target_by_name(name).is_some() {
+            bail!("Target {name:?} already exists");
+        }
+
+        let project = self.root_project_object()?;
+        let project_list = project
+            .get_string("buildConfigurationList")
+            .and_then(|id| self.objects.get_owned::<XCConfigurationListData>(id).ok())
+            .unwrap_or_default();
+        let mut project_configurations = project_list
+            .build_configurations
+            .iter()
+            .flat_map(|id| self.objects.get_owned::<XCBuildConfigurationData>(id).ok())
+            .collect::<Vec<_>>();
+        if project_configurations.is_empty() {
+            project_configurations = vec![
+                XCBuildConfigurationData::new("Debug"),
+                XCBuildConfigurationData::new("Release"),
+            ];
+        }
+        let created_on_tools_version = project
+            .get_object("attributes")
+            .and_then(|a| a.get_object("TargetAttributes"))
+            .and_then(|a| {
+                a.values()
+                    .flat_map(|v| v.as_object()?.get_value("CreatedOnToolsVersion"))
+                    .next()
+            })
+            .cloned();
+        let bundle_identifier_prefix = self.bundle_identifier_prefix();
+        let deployment_target = self.deployment_target(&platform, &project_configurations);
+
+        let configurations = project_configurations
+            .into_iter()
+            .map(|project_configuration| {
+                let mut configuration = XCBuildConfigurationData::new(project_configuration.name);
+                configuration.build_settings = default_build_settings(
+                    name,
+                    &product_type,
+                    &platform,
+                    deployment_target.as_ref(),
+                    &bundle_identifier_prefix,
+                );
+                self.objects.push(configuration)
+            })
+            .collect();
+        let mut list = XCConfigurationListData::new(configurations);
+        list.default_configuration_name = project_list
+            .default_configuration_name
+            .or_else(|| Some("Release".into()));
+        let list = self.objects.push(list);
+
+        let build_phases = [
+            PBXBuildPhaseKind::Sources,
+            PBXBuildPhaseKind::Frameworks,
+            PBXBuildPhaseKind::Resources,
+        ]
+        .into_iter()
+        .map(|kind| self.objects.push(PBXBuildPhaseData::new(kind)))
+        .collect();
+
+        let product = self.objects.push(PBXFSReferenceData {
+            kind: PBXFSReferenceKind::File,
+            path: Some(product_type.file_name(name)),
+            source_tree: PBXSourceTree::BuildProductsDir,
+            explicit_file_type: Some(product_type.explicit_file_type().to_string()),
+            include_in_index: Some(false),
+            ..Default::default()
+        });
+        let products_group = self.products_group()?;
+        self.objects.update(&products_group, |group| {
+            vec_entry(group, "children").0.push(product.clone().into())
+        });
+
+        let mut target = PBXTargetData::new(name, product_type);
+        target.build_configuration_list = Some(list);
+        target.build_phases = build_phases;
+        target.product_reference = Some(product);
+        let id = self.objects.push(target);
+
+        self.update_root_project(|project| {
+            vec_entry(project, "targets").0.push(id.clone().into());
+
+            let mut attributes = PBXHashMap::default();
+            if let Some(version) = created_on_tools_version {
+                attributes.insert_value("CreatedOnToolsVersion", version);
+            }
+            object_entry(object_entry(project, "attributes"), "TargetAttributes")
+                .insert_value(id.clone(), attributes);
+        })?;
+
+        Ok(id)
+    }
+
+    /// Duplicate target with a given name as `new_name`, returning id of the copy.
+    ///
+    /// Configuration list, configurations, build phases, build files, build rules, dependencies
+    /// and package product dependencies are copied with new ids, while build files keep pointing
+    /// at the same file references. The copy gets its own product reference named after
+    /// `new_name` and is registered next to the original in the root project targets. Copies are
+    /// only inserted once all of them resolved, so a failure leaves the project unchanged.
+    pub fn duplicate_target(&mut self, name: &str, new_name: &str) -> Result<String> {
+        if self.objects.get_target_by_name(new_name).is_some() {
+            bail!("Target {new_name:?} already exists");
+        }
+        let source_id = self
+            .objects
+            .get_target_by_name(name)
+            .ok_or_else(|| anyhow!("Target {name:?} doesn't exists!"))?
+            .id
+            .clone();
+        let source = self.objects.get_owned::<PBXTargetData>(&source_id)?;
+        let product_name = source
+            .product_name
+            .clone()
+            .unwrap_or_else(|| source.name.clone());
+        self.root_project_object()?;
+
+        let mut pending = PendingObjects::default();
+        let mut products = HashMap::new();
+        for id in source.package_product_dependencies.iter() {
+            products.insert(id.clone(), self.duplicate_object(id, &mut pending)?);
+        }
+
+        let mut target = source.clone();
+        target.name = new_name.into();
+        target.product_name = Some(new_name.into());
+        target.package_product_dependencies = source
+            .package_product_dependencies
+            .iter()
+            .map(|id| products[id].clone())
+            .collect();
+        target.build_configuration_list = source
+            .build_configuration_list
+            .as_ref()
+            .map(|id| self.duplicate_configuration_list(id, &product_name, new_name, &mut pending))
+            .transpose()?;
+        target.build_phases = source
+            .build_phases
+            .iter()
+            .map(|id| self.duplicate_build_phase(id, &products, &mut pending))
+            .collect::<Result<_>>()?;
+        target.build_rules = source
+            .build_rules
+            .iter()
+            .map(|id| self.duplicate_object(id, &mut pending))
+            .collect::<Result<_>>()?;
+        target.dependencies = source
+            .dependencies
+            .iter()
+            .map(|id| self.duplicate_dependency(id, &products, &mut pending))
+            .collect::<Result<_>>()?;
+        target.product_reference = source
+            .product_reference
+            .as_ref()
+            .map(|id| self.duplicate_product(id, &product_name, new_name, &mut pending))
+            .transpose()?;
+        // Resolve the group of the product copy before inserting anything
+        let product = match (&source.product_reference, &target.product_reference) {
+            (Some(source_product), Some(product)) => {
+                let group = match self.objects.get_fs_reference_parent(source_product) {
+                    Some(group) => group.id.clone(),
+                    None => self.products_group()?,
+                };
+                Some((group, source_product, product.clone()))
+            }
+            _ => None,
+        };
+        let id = pending.push(&self.objects, target);
+
+        for (id, object) in pending.0 {
+            self.objects.insert(id, object);
+        }
+        if let Some((group, source_product, product)) = product {
+            self.objects.update(&group, |group| {
+                let children = vec_entry(group, "children");
+                let position = children
+                    .iter()
+                    .position(|c| c.as_string() == Some(source_product))
+                    .map(|index| index + 1)
+                    .unwrap_or(children.len());
+                children.0.insert(position, product.into());
+            });
+        }
+        self.update_root_project(|project| {
+            let targets = vec_entry(project, "targets");
+            let position = targets
+                .iter()
+                .position(|t| t.as_string() == Some(&source_id))
+                .map(|index| index + 1)
+                .unwrap_or(targets.len());
+            targets.0.insert(position, id.clone().into());
+
+            let attributes = object_entry(object_entry(project, "attributes"), "TargetAttributes");
+            if let Some(source_attributes) = attributes.get_value(&source_id).cloned() {
+                attributes.insert_value(id.clone(), source_attributes);
+            }
+        })?;
+
+        Ok(id)
+    }
+
+    /// Stage a copy of object with a given id, returning the id of the copy
+    fn duplicate_object(&self, id: &str, pending: &mut PendingObjects) -> Result<String> {
+        let object = self
+            .objects
+            .get_object(id)
+            .ok_or_else(|| anyhow!("{id:?} doesn't exists!"))?
+            .clone();
+        Ok(pending.push(&self.objects, object))
+    }
+
+    /// Stage copy of configuration list and its configurations, renaming literal `PRODUCT_NAME`
+    fn duplicate_configuration_list(
+        &self,
+        id: &str,
+        product_name: &str,
+        new_name: &str,
+        pending: &mut PendingObjects,
+    ) -> Result<String> {
+        let mut list = self.objects.get_owned::<XCConfigurationListData>(id)?;
+        list.build_configurations = list
+            .build_configurations
+            .iter()
+            .map(|id| {
+                let mut configuration = self.objects.get_owned::<XCBuildConfigurationData>(id)?;
+                let settings = &mut configuration.build_settings;
+                if settings.get_str("PRODUCT_NAME") == Some(product_name) {
+                    settings.insert_value("PRODUCT_NAME", new_name);
+                }
+                Ok(pending.push(&self.objects, configuration))
+            })
+            .collect::<Result<_>>()?;
+        Ok(pending.push(&self.objects, list))
+    }
+
+    /// Stage copy of build phase with fresh build files, remapping package products with
+    /// `products`
+    fn duplicate_build_phase(
+        &self,
+        id: &str,
+        products: &HashMap<String, String>,
+        pending: &mut PendingObjects,
+    ) -> Result<String> {
+        let mut phase = self.objects.get_owned::<PBXBuildPhaseData>(id)?;
+        phase.files = phase
+            .files
+            .iter()
+            .map(|id| {
+                let mut file = self.objects.get_owned::<PBXBuildFileData>(id)?;
+                if let Some(product) = file.product_ref.as_ref().and_then(|p| products.get(p)) {
+                    file.product_ref = Some(product.clone());
+                }
+                Ok(pending.push(&self.objects, file))
+            })
+            .collect::<Result<_>>()?;
+        Ok(pending.push(&self.objects, phase))
+    }
+
+    /// Stage copy of target dependency along with its container item proxy
+    fn duplicate_dependency(
+        &self,
+        id: &str,
+        products: &HashMap<String, String>,
+        pending: &mut PendingObjects,
+    ) -> Result<String> {
+        let mut dependency = self.objects.get_owned::<PBXTargetDependencyData>(id)?;
+        dependency.target_proxy = dependency
+            .target_proxy
+            .as_ref()
+            .map(|id| self.duplicate_object(id, pending))
+            .transpose()?;
+        if let Some(product) = dependency
+            .product_ref
+            .as_ref()
+            .and_then(|p| products.get(p))
+        {
+            dependency.product_ref = Some(product.clone());
+        }
+        Ok(pending.push(&self.objects, dependency))
+    }
+
+    /// Stage copy of product file reference renamed after `new_name`
+    fn duplicate_product(
+        &self,
+        id: &str,
+        product_name: &str,
+        new_name: &str,
+        pending: &mut PendingObjects,
+    ) -> Result<String> {
+        let mut product = self.objects.get_owned::<PBXFSReferenceData>(id)?;
+        let rename = |value: &mut Option<String>| {
+            if let Some(value) = value.as_mut() {
+                *value = value.replacen(product_name, new_name, 1);
+            }
+        };
+        rename(&mut product.path);
+        rename(&mut product.name);
+        Ok(pending.push(&self.objects, product))
+    }
+
+    /// Get id of the Products group, creating it if the project has none
+    fn products_group(&mut self) -> Result<String> {
+        let project = self.root_project_object()?;
+        if let Some(id) = project.get_string("productRefGroup") {
+            return Ok(id.clone());
+        }
+
+        let main_group = project.try_get_string("mainGroup")?.clone();
+        let existing = self
+            .objects
+            .get_object(&main_group)
+            .and_then(|group| group.get_vec("children"))
+            .map(|children| children.as_vec_strings())
+            .unwrap_or_default()
+            .into_iter()
+            .find(|id| {
+                self.objects
+                    .get_object(id)
+                    .filter(|child| {
+                        child.get_kind("isa").map(|k| k.is_pbx_fsreference()) == Some(true)
+                    })
+                    .and_then(|child| {
+                        child
+                            .get_string("name")
+                            .or_else(|| child.get_string("path"))
+                    })
+                    .map(|name| name == "Products")
+                    .unwrap_or_default()
+            })
+            .cloned();
+
+        let id = match existing {
+            Some(id) => id,
+            None => {
+                let id = self
+                    .objects
+                    .push(PBXFSReferenceData::group("Products", vec![]));
+                self.objects.update(&main_group, |group| {
+                    vec_entry(group, "children").0.push(id.clone().into())
+                });
+                id
+            }
+        };
+        self.update_root_project(|project| project.insert_value("productRefGroup", id.clone()))?;
+
+        Ok(id)
+    }
+
+    /// Get deployment target of a given platform, set in project configurations or else in any
+    /// target configuration.
+    fn deployment_target(
+        &self,
+        platform: &PBXTargetPlatform,
+        project_configurations: &[XCBuildConfigurationData],
+    ) -> Option<PBXValue> {
+        let key = platform.deployment_target_setting()?;
+        project_configurations
+            .iter()
+            .map(|configuration| &configuration.build_settings)
+            .chain(
+                self.build_configurations()
+                    .into_iter()
+                    .map(|c| c.build_settings),
+            )
+            .find_map(|settings| settings.get_value(key))
+            .cloned()
+    }
+
+    /// Get prefix of existing bundle identifiers, i.e. `com.company`
+    fn bundle_identifier_prefix(&self) -> String {
+        self.objects
+            .values()
+            .filter(|object| object.get_kind("isa") == Some(&PBXObjectKind::XCBuildConfiguration))
+            .flat_map(|object| {
+                object
+                    .get_object("buildSettings")?
+                    .get_str("PRODUCT_BUNDLE_IDENTIFIER")
+            })
+            .filter(|identifier| !identifier.contains('$'))
+            .find_map(|identifier| Some(identifier.rsplit_once('.')?.0.to_string()))
+            .unwrap_or_else(|| "com.example".into())
+    }
+}
+
+impl PBXObjectCollection {
+    /// Remove target with a given id along with objects that exclusively belong to it,
+    /// returning the removed target.
+    ///
+    /// Its configuration list, configurations, build phases, build files, build rules and
+    /// product reference are removed, as well as dependencies of other targets on it, its
+    /// `TargetAttributes` entry and package product dependencies no longer used elsewhere.
+    pub fn remove_target(&mut self, id: &str) -> Result<PBXTargetData> {
+        let target = self.get_owned::<PBXTargetData>(id)?;
+        let mut removed = HashSet::from([id.to_string()]);
+
+        if let Some(list) = target.build_configuration_list.as_ref() {
+            let configurations = self.get_owned::<XCConfigurationListData>(list)?;
+            removed.extend(configurations.build_configurations);
+            removed.insert(list.clone());
+        }
+        for phase in target.build_phases.iter() {
+            removed.extend(self.get_owned::<PBXBuildPhaseData>(phase)?.files);
+            removed.insert(phase.clone());
+        }
+        removed.extend(target.build_rules.iter().cloned());
+
+        for (object_id, object) in self.iter() {
+            match object.get_kind("isa") {
+                // Build files of other targets linking or embedding the product
+                Some(PBXObjectKind::PBXBuildFile)
+                    if target.product_reference.is_some()
+                        && object.get_str("fileRef") == target.product_reference.as_deref() =>
+                {
+                    removed.insert(object_id.clone());
+                }
+                Some(PBXObjectKind::PBXTargetDependency) => {
+                    let proxy = object.get_string("targetProxy");
+                    let remote_id = proxy
+                        .and_then(|proxy| self.get_object(proxy))
+                        .and_then(|proxy| proxy.get_str("remoteGlobalIDString"));
+                    if target.dependencies.contains(object_id)
+                        || object.get_str("target") == Some(id)
+                        || remote_id == Some(id)
+                    {
+                        removed.insert(object_id.clone());
+                        removed.extend(proxy.cloned());
+                    }
+                }
+                _ => {}
+            }
+        }
+        removed.extend(target.product_reference.iter().cloned());
+
+        self.remove_linked(&removed);
+
+        let projects = self
+            .iter()
+            .filter(|(_, object)| object.get_kind("isa") == Some(&PBXObjectKind::PBXProject))
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        for project in projects {
+            self.update(&project, |project| {
+                let attributes = project
+                    .get_value_mut("attributes")
+                    .and_then(PBXValue::as_object_mut)
+                    .and_then(|attributes| attributes.get_value_mut("TargetAttributes"))
+                    .and_then(PBXValue::as_object_mut);
+                if let Some(attributes) = attributes {
+                    attributes.0.shift_remove(id);
+                    for target_attributes in
+                        attributes.0.values_mut().flat_map(PBXValue::as_object_mut)
+                    {
+                        if target_attributes.get_str("TestTargetID") == Some(id) {
+                            target_attributes.remove_value("TestTargetID");
+                        }
+                    }
+                }
+            });
+        }
+
+        for product in target.package_product_dependencies.iter() {
+            if !self.is_referenced(product) {
+                self.remove(product);
+            }
+        }
+
+        Ok(target)
+    }
+}
+
+/// Default target build settings
+fn default_build_settings(
+    name: &str,
+    product_type: &PBXProductType,
+    platform: &PBXTargetPlatform,
+    deployment_target: Option<&PBXValue>,
+    bundle_identifier_prefix: &str,
+) -> PBXHashMap {
+    use PBXProductType::*;
+
+    let mut settings = PBXHashMap::default();
+    let extension = product_type.file_extension();
+    let is_bundle = matches!(
+        extension,
+        Some("app" | "appex" | "framework" | "xctest" | "bundle" | "xpc")
+    );
+
+    settings.insert_value("CODE_SIGN_STYLE", "Automatic");
+    settings.insert_value("PRODUCT_NAME", "$(TARGET_NAME)");
+    settings.insert_value("SWIFT_VERSION", PBXNumber::new("5.0"));
+
+    if is_bundle {
+        let identifier = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect::<String>();
+        settings.insert_value(
+            "PRODUCT_BUNDLE_IDENTIFIER",
+            format!("{bundle_identifier_prefix}.{identifier}"),
+        );
+        settings.insert_value("GENERATE_INFOPLIST_FILE", true);
+        settings.insert_value("CURRENT_PROJECT_VERSION", PBXNumber::new("1"));
+        settings.insert_value("MARKETING_VERSION", PBXNumber::new("1.0"));
+    }
+
+    if matches!(extension, Some("app" | "appex" | "xctest" | "xpc")) {
+        let frameworks = if platform.is_mac_os() {
+            "@executable_path/../Frameworks"
+        } else {
+            "@executable_path/Frameworks"
+        };
+        settings.insert_value("LD_RUNPATH_SEARCH_PATHS", vec!["$(inherited)", frameworks]);
+    }
+
+    match product_type {
+        Framework | StaticFramework => {
+            settings.insert_value("DEFINES_MODULE", true);
+            settings.insert_value("SKIP_INSTALL", true);
+            settings.insert_value("VERSIONING_SYSTEM", "apple-generic");
+        }
+        StaticLibrary | DynamicLibrary => {
+            settings.insert_value("SKIP_INSTALL", true);
+        }
+        _ => {}
+    }
+
+    if let Some(sdk_root) = platform.sdk_root() {
+        settings.insert_value("SDKROOT", sdk_root);
+    }
+    if let Some((key, version)) = platform.deployment_target_setting().zip(deployment_target) {
+        settings.insert_value(key, version.clone());
+    }
+    if platform.is_ios() {
+        settings.insert_value("TARGETED_DEVICE_FAMILY", "1,2");
+    }
+
+    settings.0.sort_keys();
+    settings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbxproj::test_demo_file;
+
+    #[test]
+    fn add_native_target() {
+        let mut project = test_demo_file!(demo1);
+        let id = project
+            .add_native_target(
+                "Wordle Tests",
+                PBXProductType::UnitTestBundle,
+                PBXTargetPlatform::IOS,
+            )
+            .unwrap();
+
+        let target = project.get_target_by_name("Wordle Tests").unwrap();
+        assert_eq!(target.id, id);
+        assert_eq!(target.kind, &PBXTargetKind::Native);
+        assert_eq!(target.info(&project).platform, PBXTargetPlatform::IOS);
+        assert_eq!(
+            target
+                .build_phases
+                .iter()
+                .map(|p| p.kind.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                PBXBuildPhaseKind::Sources,
+                PBXBuildPhaseKind::Frameworks,
+                PBXBuildPhaseKind::Resources
+            ]
+        );
+
+        let list = target.build_configuration_list.as_ref().unwrap();
+        let names = list
+            .build_configurations
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Debug", "Release"]);
+        let settings = list.build_configurations[0].build_settings;
+        assert_eq!(
+            settings.get_string("PRODUCT_BUNDLE_IDENTIFIER").unwrap(),
+            "tami5.Wordle-Tests"
+        );
+        assert_eq!(settings.get_str("IPHONEOS_DEPLOYMENT_TARGET"), Some("15.0"));
+
+        let product = target.product.as_ref().unwrap();
+        assert_eq!(product.path.unwrap(), "Wordle Tests.xctest");
+        let products = project.get_fs_reference_parent(&product.id).unwrap();
+        assert_eq!(products.name.unwrap(), "Products");
+
+        let root = project.root_project();
+        assert!(root.targets.iter().any(|t| t.id == id));
+        assert!(root.get_attributes_for_target_reference(&id).is_some());
+        assert_eq!(
+            project
+                .root_project_object()
+                .unwrap()
+                .get_string("productRefGroup"),
+            Some(&products.id)
+        );
+    }
+
+    #[test]
+    fn add_static_library() {
+        let mut project = test_demo_file!(demo4);
+        let project_products = project
+            .root_project_object()
+            .unwrap()
+            .get_string("productRefGroup")
+            .cloned();
+        project
+            .add_native_target(
+                "Core",
+                PBXProductType::StaticLibrary,
+                PBXTargetPlatform::IOS,
+            )
+            .unwrap();
+
+        let target = project.get_target_by_name("Core").unwrap();
+        let product = target.product.as_ref().unwrap();
+        assert_eq!(product.path.unwrap(), "libCore.a");
+        assert_eq!(product.explicit_file_type.unwrap(), "archive.ar");
+        assert_eq!(
+            project.get_fs_reference_parent(&product.id).map(|g| g.id),
+            project_products
+        );
+
+        let error = project
+            .add_native_target(
+                "Core",
+                PBXProductType::StaticLibrary,
+                PBXTargetPlatform::IOS,
+            )
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Target \"Core\" already exists");
+    }
+
+    #[test]
+    fn duplicate_target() {
+        let mut project = test_demo_file!(demo1);
+        let source_id = project.get_target_by_name("Wordle").unwrap().id.clone();
+        let source = project.get_owned::<PBXTargetData>(&source_id).unwrap();
+        let id = project.duplicate_target("Wordle", "Wordle Beta").unwrap();
+        let copy = project.get_owned::<PBXTargetData>(&id).unwrap();
+
+        assert_eq!(copy.product_name.as_deref(), Some("Wordle Beta"));
+        assert_eq!(copy.build_phases.len(), source.build_phases.len());
+        assert_ne!(
+            copy.build_configuration_list,
+            source.build_configuration_list
+        );
+        assert_eq!(copy.package_product_dependencies.len(), 1);
+        assert_ne!(
+            copy.package_product_dependencies,
+            source.package_product_dependencies
+        );
+
+        for (phase, source_phase) in copy.build_phases.iter().zip(&source.build_phases) {
+            assert_ne!(phase, source_phase);
+            let phase = project.get_owned::<PBXBuildPhaseData>(phase).unwrap();
+            let source_phase = project
+                .get_owned::<PBXBuildPhaseData>(source_phase)
+                .unwrap();
+            for (file, source_file) in phase.files.iter().zip(&source_phase.files) {
+                assert_ne!(file, source_file);
+                let file = project.get_owned::<PBXBuildFileData>(file).unwrap();
+                let source_file = project.get_owned::<PBXBuildFileData>(source_file).unwrap();
+                assert_eq!(file.file_ref, source_file.file_ref);
+                if file.product_ref.is_some() {
+                    assert_eq!(
+                        file.product_ref.as_ref(),
+                        copy.package_product_dependencies.first()
+                    );
+                }
+            }
+        }
+
+        let target = project.get_target_by_name("Wordle Beta").unwrap();
+        let product = target.product.as_ref().unwrap();
+        assert_eq!(product.path.unwrap(), "Wordle Beta.app");
+        assert_eq!(
+            project.get_fs_reference_parent(&product.id).map(|g| g.id),
+            project
+                .get_fs_reference_parent(source.product_reference.as_ref().unwrap())
+                .map(|g| g.id)
+        );
+        let names = target
+            .build_configuration_list
+            .as_ref()
+            .unwrap()
+            .build_configurations
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Debug", "Release"]);
+
+        let root = project.root_project();
+        let targets = root
+            .targets
+            .iter()
+            .map(|t| t.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(targets, vec![source_id.as_str(), id.as_str()]);
+        assert!(root.get_attributes_for_target_reference(&id).is_some());
+
+        let error = project
+            .duplicate_target("Wordle", "Wordle Beta")
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Target \"Wordle Beta\" already exists");
+    }
+
+    #[test]
+    fn duplicate_target_with_dependencies() {
+        let mut project = test_demo_file!(demo4);
+        let source_id = project
+            .get_target_by_name("BackbaseCXPTests")
+            .unwrap()
+            .id
+            .clone();
+        let source = project.get_owned::<PBXTargetData>(&source_id).unwrap();
+        let id = project
+            .duplicate_target("BackbaseCXPTests", "BackbaseCXPUITests")
+            .unwrap();
+        let copy = project.get_owned::<PBXTargetData>(&id).unwrap();
+
+        assert_eq!(copy.dependencies.len(), 2);
+        for (dependency, source_dependency) in copy.dependencies.iter().zip(&source.dependencies) {
+            let dependency = project
+                .get_owned::<PBXTargetDependencyData>(dependency)
+                .unwrap();
+            let source_dependency = project
+                .get_owned::<PBXTargetDependencyData>(source_dependency)
+                .unwrap();
+            assert_eq!(dependency.target, source_dependency.target);
+            assert_ne!(dependency.target_proxy, source_dependency.target_proxy);
+        }
+
+        let product = project
+            .get_owned::<PBXFSReferenceData>(copy.product_reference.as_ref().unwrap())
+            .unwrap();
+        assert_eq!(product.path.unwrap(), "BackbaseCXPUITests.xctest");
+    }
+
+    #[test]
+    fn duplicate_target_failure_leaves_project_unchanged() {
+        let mut project = test_demo_file!(demo1);
+        let source = project.get_target_by_name("Wordle").unwrap();
+        let phase = source.build_phases.last().unwrap();
+        let file = phase.files.last().unwrap().id.clone();
+        project.objects_mut().remove(&file).unwrap();
+
+        let before = project.keys().cloned().collect::<Vec<_>>();
+        let root = project.get_object(&project.root_object_reference).cloned();
+        let error = project
+            .duplicate_target("Wordle", "Wordle Beta")
+            .unwrap_err();
+        assert_eq!(error.to_string(), format!("{file:?} doesn't exists!"));
+        assert_eq!(project.keys().cloned().collect::<Vec<_>>(), before);
+        assert_eq!(
+            project.get_object(&project.root_object_reference).cloned(),
+            root
+        );
+    }
+
+    fn assert_no_dangling_ids(project: &PBXRootObject, before: &[String]) {
+        for id in before.iter().filter(|id| project.get_object(id).is_none()) {
+            assert!(!project.is_referenced(id), "{id} is still referenced");
+        }
+    }
+
+    #[test]
+    fn remove_target() {
+        let mut project = test_demo_file!(demo8);
+        let before = project.keys().cloned().collect::<Vec<_>>();
+        let id = project.get_target_by_name("test").unwrap().id.clone();
+        let target = project.objects_mut().remove_target(&id).unwrap();
+
+        assert_eq!(target.name, "test");
+        assert!(project.get_target_by_name("test").is_none());
+        for removed in target
+            .build_phases
+            .iter()
+            .chain(target.build_configuration_list.iter())
+            .chain(target.product_reference.iter())
+        {
+            assert!(project.get_object(removed).is_none());
+        }
+        assert_no_dangling_ids(&project, &before);
+
+        let tests = project.get_target_by_name("testTests").unwrap().id.clone();
+        let tests = project.get_owned::<PBXTargetData>(&tests).unwrap();
+        assert!(tests.dependencies.is_empty());
+        assert_eq!(project.root_project().targets.len(), 1);
+
+        let attributes = project
+            .root_project_object()
+            .unwrap()
+            .get_object("attributes")
+            .and_then(|a| a.get_object("TargetAttributes"))
+            .unwrap();
+        assert!(attributes.get_value(&id).is_none());
+        assert!(attributes.values().all(|a| a
+            .as_object()
+            .unwrap()
+            .get_value("TestTargetID")
+            .is_none()));
+    }
+
+    #[test]
+    fn remove_target_with_package_product() {
+        let mut project = test_demo_file!(demo1);
+        let before = project.keys().cloned().collect::<Vec<_>>();
+        let id = project.get_target_by_name("Wordle").unwrap().id.clone();
+        project.objects_mut().remove_target(&id).unwrap();
+
+        assert!(project.get_object("AB54E6499A232290AC0E2033").is_none());
+        assert!(project.get_object("551B59949C78B8B2DF5A3412").is_none());
+        assert!(project.root_project().targets.is_empty());
+        assert_no_dangling_ids(&project, &before);
+    }
+}
@@ -0,0 +1,216 @@
+//! OpenStep (old-style plist) string escaping as used by pbxproj files
+use anyhow::{bail, Result};
+use std::borrow::Cow;
+
+/// Decode escape sequences of a quoted string, given its content without surrounding quotes.
+///
+/// Supports `\a`, `\b`, `\f`, `\n`, `\r`, `\t`, `\v`, `\"`, `\'`, `\\`, `\Uxxxx` and octal
+/// `\ooo` sequences. UTF-16 surrogate pairs, i.e. `\UD83D\UDE00`, decode to a single char, and
+/// octal bytes above ASCII decode through the NeXTSTEP encoding as Xcode does. Any other escaped
+/// character decodes to itself. Returns borrowed input when no decoding is needed.
+pub fn unescape_string(raw: &str) -> Result<Cow<'_, str>> {
+    if !raw.contains('\\') {
+        return Ok(Cow::Borrowed(raw));
+    }
+
+    let mut output = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+
+        let escaped = match chars.next() {
+            Some(escaped) => escaped,
+            None => bail!("Dangling escape at end of {raw:?}"),
+        };
+
+        match escaped {
+            'a' => output.push('\x07'),
+            'b' => output.push('\x08'),
+            'f' => output.push('\x0c'),
+            'n' => output.push('\n'),
+            'r' => output.push('\r'),
+            't' => output.push('\t'),
+            'v' => output.push('\x0b'),
+            'U' | 'u' => {
+                let read_unit = |chars: &mut dyn Iterator<Item = char>, escaped| {
+                    let hex = chars.take(4).collect::<String>();
+                    match u16::from_str_radix(&hex, 16)
+                        .ok()
+                        .filter(|_| hex.len() == 4)
+                    {
+                        Some(unit) => Ok(unit),
+                        None => bail!("Invalid unicode escape \\{escaped}{hex} in {raw:?}"),
+                    }
+                };
+                let mut units = vec![read_unit(&mut chars, escaped)?];
+                if (0xD800..0xDC00).contains(&units[0]) {
+                    match (chars.next(), chars.next()) {
+                        (Some('\\'), Some(escaped @ ('U' | 'u'))) => {
+                            units.push(read_unit(&mut chars, escaped)?)
+                        }
+                        _ => bail!("Unpaired surrogate \\{escaped}{:04X} in {raw:?}", units[0]),
+                    }
+                }
+                match char::decode_utf16(units).collect::<Result<String, _>>() {
+                    Ok(decoded) => output.push_str(&decoded),
+                    Err(err) => bail!("Invalid unicode escape in {raw:?}: {err}"),
+                }
+            }
+            '0'..='7' => {
+                let mut code = escaped.to_digit(8).unwrap_or_default();
+                for _ in 0..2 {
+                    match chars.peek().and_then(|c| c.to_digit(8)) {
+                        Some(digit) => {
+                            code = code * 8 + digit;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+                match code {
+                    0..=0x7f => output.push(char::from_u32(code).unwrap_or_default()),
+                    0x80..=0xff => output.push(NEXTSTEP_CHARS[code as usize - 0x80]),
+                    _ => bail!("Invalid octal escape \\{code:o} in {raw:?}"),
+                }
+            }
+            escaped => output.push(escaped),
+        }
+    }
+
+    Ok(Cow::Owned(output))
+}
+
+/// Unicode chars of NeXTSTEP encoded bytes from `0x80` to `0xff`
+#[rustfmt::skip]
+const NEXTSTEP_CHARS: [char; 128] = [
+    '\u{00A0}', '\u{00C0}', '\u{00C1}', '\u{00C2}', '\u{00C3}', '\u{00C4}', '\u{00C5}', '\u{00C7}',
+    '\u{00C8}', '\u{00C9}', '\u{00CA}', '\u{00CB}', '\u{00CC}', '\u{00CD}', '\u{00CE}', '\u{00CF}',
+    '\u{00D0}', '\u{00D1}', '\u{00D2}', '\u{00D3}', '\u{00D4}', '\u{00D5}', '\u{00D6}', '\u{00D9}',
+    '\u{00DA}', '\u{00DB}', '\u{00DC}', '\u{00DD}', '\u{00DE}', '\u{00B5}', '\u{00D7}', '\u{00F7}',
+    '\u{00A9}', '\u{00A1}', '\u{00A2}', '\u{00A3}', '\u{2044}', '\u{00A5}', '\u{0192}', '\u{00A7}',
+    '\u{00A4}', '\u{2019}', '\u{201C}', '\u{00AB}', '\u{2039}', '\u{203A}', '\u{FB01}', '\u{FB02}',
+    '\u{00AE}', '\u{2013}', '\u{2020}', '\u{2021}', '\u{00B7}', '\u{00A6}', '\u{00B6}', '\u{2022}',
+    '\u{201A}', '\u{201E}', '\u{201D}', '\u{00BB}', '\u{2026}', '\u{2030}', '\u{00AC}', '\u{00BF}',
+    '\u{00B9}', '\u{02CB}', '\u{00B4}', '\u{02C6}', '\u{02DC}', '\u{00AF}', '\u{02D8}', '\u{02D9}',
+    '\u{00A8}', '\u{00B2}', '\u{02DA}', '\u{00B8}', '\u{00B3}', '\u{02DD}', '\u{02DB}', '\u{02C7}',
+    '\u{2014}', '\u{00B1}', '\u{00BC}', '\u{00BD}', '\u{00BE}', '\u{00E0}', '\u{00E1}', '\u{00E2}',
+    '\u{00E3}', '\u{00E4}', '\u{00E5}', '\u{00E7}', '\u{00E8}', '\u{00E9}', '\u{00EA}', '\u{00EB}',
+    '\u{00EC}', '\u{00C6}', '\u{00ED}', '\u{00AA}', '\u{00EE}', '\u{00EF}', '\u{00F0}', '\u{00F1}',
+    '\u{0141}', '\u{00D8}', '\u{0152}', '\u{00BA}', '\u{00F2}', '\u{00F3}', '\u{00F4}', '\u{00F5}',
+    '\u{00F6}', '\u{00E6}', '\u{00F9}', '\u{00FA}', '\u{00FB}', '\u{0131}', '\u{00FC}', '\u{00FD}',
+    '\u{0142}', '\u{00F8}', '\u{0153}', '\u{00DF}', '\u{00FE}', '\u{00FF}', '\u{FFFD}', '\u{FFFD}',
+];
+
+/// Encode a string for writing to a pbxproj file, quoting and escaping it when needed.
+///
+/// Inverse of [`unescape_string`] for quoted strings.
+pub fn escape_string(value: &str) -> Cow<'_, str> {
+    let is_plain = !value.is_empty()
+        && !value.contains("//")
+        && !value.contains("___")
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"_$/:.".contains(&b));
+
+    if is_plain {
+        return Cow::Borrowed(value);
+    }
+
+    let mut output = String::with_capacity(value.len() + 2);
+    output.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => output.push_str("\\\\"),
+            '"' => output.push_str("\\\""),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if c.is_ascii_control() => output.push_str(&format!("\\U{:04x}", c as u32)),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+
+    Cow::Owned(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbxproj::test_demo_file;
+
+    #[test]
+    fn unescape() {
+        assert!(matches!(
+            unescape_string("plain").unwrap(),
+            Cow::Borrowed(_)
+        ));
+        assert_eq!(
+            unescape_string(r#"echo \"a\\b\"\n\tdone"#).unwrap(),
+            "echo \"a\\b\"\n\tdone"
+        );
+        assert_eq!(unescape_string(r"\U00e9t\335").unwrap(), "été");
+        assert_eq!(unescape_string(r"\101\200\351\274").unwrap(), "A\u{a0}Ø…");
+        assert_eq!(
+            unescape_string(r"smile \UD83D\uDE00!").unwrap(),
+            "smile 😀!"
+        );
+        assert!(unescape_string(r"\UD83D").is_err());
+        assert!(unescape_string(r"\UD83Dx").is_err());
+        assert!(unescape_string(r"\UDE00").is_err());
+        assert!(unescape_string(r"\777").is_err());
+        assert_eq!(unescape_string(r"\$(SRCROOT)").unwrap(), "$(SRCROOT)");
+        assert!(unescape_string(r"\U00").is_err());
+        assert!(unescape_string("dangling\\").is_err());
+    }
+
+    #[test]
+    fn escape() {
+        assert_eq!(escape_string("Source/File.swift"), "Source/File.swift");
+        assert_eq!(escape_string(""), "\"\"");
+        assert_eq!(escape_string("<group>"), "\"<group>\"");
+        assert_eq!(escape_string("a\u{1}b"), "\"a\\U0001b\"");
+
+        let script = "echo \"${SRCROOT}\" \\\n\tdone";
+        let escaped = escape_string(script);
+        assert_eq!(escaped, r#""echo \"${SRCROOT}\" \\\n\tdone""#);
+        assert_eq!(
+            unescape_string(&escaped[1..escaped.len() - 1]).unwrap(),
+            script
+        );
+    }
+
+    #[test]
+    fn multi_line_run_scripts() {
+        let project = test_demo_file!(demo3);
+        let script = project
+            .values()
+            .find_map(|v| v.get_string("shellScript"))
+            .unwrap();
+        assert_eq!(
+            script,
+            "echo \"${BUILT_PRODUCTS_DIR}/${FRAMEWORKS_FOLDER_PATH}\"\n\
+             bash \"${BUILT_PRODUCTS_DIR}/${FRAMEWORKS_FOLDER_PATH}/BackbaseCXP.framework/strip-frameworks.sh\""
+        );
+
+        let project = test_demo_file!(demo4);
+        let mut scripts = project
+            .values()
+            .flat_map(|v| v.get_string("shellScript"))
+            .collect::<Vec<_>>();
+        scripts.sort();
+        assert_eq!(
+            scripts[0],
+            "# remove building files from the final assets.\n\
+             cd \"${CODESIGNING_FOLDER_PATH}\"/assets/backbase/portal-client-mobile\n\
+             shopt -s extglob\n\
+             rm -rf !(dist|html)\n\
+             rm -rf .gitignore .jshintrc"
+        );
+        assert_eq!(scripts[1].lines().count(), 3);
+    }
+}
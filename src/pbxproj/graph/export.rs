@@ -0,0 +1,443 @@
+use super::get_ids;
+use crate::pbxproj::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Kind of a [`PBXDependencyDiagram`] node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PBXDiagramNodeKind {
+    /// Target with its product type
+    Target(PBXProductType),
+    /// Swift package product ([`XCSwiftPackageProductDependency`])
+    PackageProduct,
+    /// Swift package ([`XCRemoteSwiftPackageReference`])
+    Package,
+    /// Framework or library linked in Frameworks build phase and not produced by a target
+    Framework,
+}
+
+/// Kind of a [`PBXDependencyDiagram`] edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PBXDiagramEdgeKind {
+    /// Dependency between nodes of [`PBXDependencyGraph`]
+    Dependency(PBXDependencyKind),
+    /// Package product provided by a swift package
+    Package,
+    /// Framework linked in Frameworks build phase
+    LinkedFramework,
+}
+
+/// Node of a [`PBXDependencyDiagram`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PBXDiagramNode {
+    /// Node label
+    pub label: String,
+    /// Node kind
+    pub kind: PBXDiagramNodeKind,
+}
+
+/// Renderable diagram of targets, swift packages and linked frameworks
+///
+/// Edges point from a node to what it depends on. Edges of a dependency cycle, if any, are
+/// highlighted in red.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PBXDependencyDiagram {
+    nodes: BTreeMap<String, PBXDiagramNode>,
+    edges: BTreeSet<(String, String, PBXDiagramEdgeKind)>,
+    cycle: BTreeSet<(String, String)>,
+}
+
+const CYCLE_DOT_COLOR: &str = "color=\"#e31a1c\"";
+const CYCLE_MERMAID_STYLE: &str = "stroke:#e31a1c,stroke-width:2px";
+
+struct NodeStyle {
+    class: &'static str,
+    shape: &'static str,
+    color: &'static str,
+}
+
+impl PBXDependencyDiagram {
+    /// Create diagram from objects, only including targets of a given platform if any.
+    ///
+    /// NOTE: Like [`PBXDependencyGraph::new`], raw objects are used instead of [`PBXTarget`], so
+    /// that a cyclic project can still be exported with its cycle highlighted.
+    pub fn new(objects: &PBXObjectCollection, platform: Option<&PBXTargetPlatform>) -> Self {
+        let mut diagram = Self::default();
+        let graph = PBXDependencyGraph::new(objects);
+        let targets = objects
+            .iter()
+            .filter(|(_, v)| {
+                v.get_kind("isa")
+                    .map(|k| k.is_pbx_target())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+        let products = targets
+            .iter()
+            .flat_map(|(_, target)| target.get_string("productReference"))
+            .collect::<HashSet<_>>();
+        let targets = targets
+            .into_iter()
+            .filter(|(_, target)| platform.is_none_or(|p| &target_platform(objects, target) == p))
+            .collect::<Vec<_>>();
+
+        for (id, target) in targets.iter() {
+            let label = target.get_string("name").unwrap_or(id);
+            let product_type = target
+                .get_string("productType")
+                .map(|t| t.as_str().into())
+                .unwrap_or_default();
+            diagram.add_node(id, label, PBXDiagramNodeKind::Target(product_type));
+        }
+
+        for (id, target) in targets.iter() {
+            let node = PBXDependencyNode::Target(id.to_string());
+            for (dependency, kind) in graph.dependencies(&node) {
+                let kind = PBXDiagramEdgeKind::Dependency(kind);
+                match dependency {
+                    PBXDependencyNode::Target(dependency) => {
+                        if diagram.nodes.contains_key(dependency) {
+                            diagram.add_edge(id, dependency, kind);
+                        }
+                    }
+                    PBXDependencyNode::PackageProduct {
+                        package,
+                        product_name,
+                    } => {
+                        let key =
+                            format!("{}:{product_name}", package.as_deref().unwrap_or_default());
+                        diagram.add_node(&key, product_name, PBXDiagramNodeKind::PackageProduct);
+                        diagram.add_edge(id, &key, kind);
+
+                        if let Some(package) = package
+                            .as_ref()
+                            .and_then(|id| objects.get::<XCRemoteSwiftPackageReference, _>(id))
+                        {
+                            let label = package.name().unwrap_or(&package.id);
+                            diagram.add_node(&package.id, label, PBXDiagramNodeKind::Package);
+                            diagram.add_edge(&key, &package.id, PBXDiagramEdgeKind::Package);
+                        }
+                    }
+                }
+            }
+
+            for (file_id, file) in get_ids(target, "buildPhases")
+                .into_iter()
+                .flat_map(|id| objects.get_object(id))
+                .filter(|phase| {
+                    phase
+                        .get_kind("isa")
+                        .and_then(|k| k.as_pbx_build_phase())
+                        .map(|k| k.is_frameworks())
+                        .unwrap_or_default()
+                })
+                .flat_map(|phase| get_ids(phase, "files"))
+                .flat_map(|id| objects.get_object(id)?.get_string("fileRef"))
+                .filter(|file_id| !products.contains(file_id))
+                .flat_map(|file_id| Some((file_id, objects.get_object(file_id)?)))
+            {
+                let label = file
+                    .get_string("name")
+                    .or(file.get_string("path"))
+                    .and_then(|p| Path::new(p).file_name()?.to_str())
+                    .unwrap_or(file_id);
+                diagram.add_node(file_id, label, PBXDiagramNodeKind::Framework);
+                diagram.add_edge(id, file_id, PBXDiagramEdgeKind::LinkedFramework);
+            }
+        }
+
+        if let Some(cycle) = graph.find_cycle() {
+            for pair in cycle.windows(2) {
+                if let [PBXDependencyNode::Target(from), PBXDependencyNode::Target(to)] = pair {
+                    if diagram.nodes.contains_key(from) && diagram.nodes.contains_key(to) {
+                        diagram.cycle.insert((from.clone(), to.clone()));
+                    }
+                }
+            }
+        }
+
+        diagram
+    }
+
+    /// Get diagram nodes by key
+    pub fn nodes(&self) -> &BTreeMap<String, PBXDiagramNode> {
+        &self.nodes
+    }
+
+    /// Get diagram edges as (from, to, kind)
+    pub fn edges(&self) -> &BTreeSet<(String, String, PBXDiagramEdgeKind)> {
+        &self.edges
+    }
+
+    /// Get edges of a dependency cycle as (from, to), highlighted when rendered
+    pub fn cycle(&self) -> &BTreeSet<(String, String)> {
+        &self.cycle
+    }
+
+    fn is_cycle_edge(&self, from: &str, to: &str) -> bool {
+        self.cycle.contains(&(from.to_string(), to.to_string()))
+    }
+
+    /// Render diagram as Graphviz DOT
+    pub fn to_dot(&self) -> String {
+        let ids = self.ids();
+        let mut output = String::from("digraph \"Dependencies\" {\n");
+        output.push_str("  rankdir=LR;\n");
+        output.push_str("  node [style=filled, fontname=\"Helvetica\"];\n");
+
+        for (key, node) in self.nodes.iter() {
+            let style = node_style(&node.kind);
+            let label = node.label.replace('\\', "\\\\").replace('"', "\\\"");
+            writeln!(
+                output,
+                "  {} [label=\"{label}\", shape={}, fillcolor=\"{}\"];",
+                ids[key.as_str()],
+                style.shape,
+                style.color
+            )
+            .ok();
+        }
+
+        for (from, to, kind) in self.edges.iter() {
+            let mut attributes = match kind {
+                PBXDiagramEdgeKind::Dependency(PBXDependencyKind::Explicit) => vec![],
+                PBXDiagramEdgeKind::Dependency(PBXDependencyKind::LinkedProduct) => {
+                    vec!["style=dashed", "label=\"links\""]
+                }
+                PBXDiagramEdgeKind::Dependency(PBXDependencyKind::PackageProduct) => {
+                    vec!["color=\"#b15928\""]
+                }
+                PBXDiagramEdgeKind::Package => vec!["style=dotted"],
+                PBXDiagramEdgeKind::LinkedFramework => vec!["style=dashed"],
+            };
+            if self.is_cycle_edge(from, to) {
+                attributes.retain(|a| !a.starts_with("color="));
+                attributes.extend([CYCLE_DOT_COLOR, "penwidth=2"]);
+            }
+            let attributes = if attributes.is_empty() {
+                String::default()
+            } else {
+                format!(" [{}]", attributes.join(", "))
+            };
+            writeln!(
+                output,
+                "  {} -> {}{attributes};",
+                ids[from.as_str()],
+                ids[to.as_str()]
+            )
+            .ok();
+        }
+
+        output.push_str("}\n");
+        output
+    }
+
+    /// Render diagram as Mermaid flowchart
+    pub fn to_mermaid(&self) -> String {
+        let ids = self.ids();
+        let mut output = String::from("flowchart LR\n");
+        let mut classes = BTreeMap::<&str, (&str, Vec<&str>)>::new();
+
+        for (key, node) in self.nodes.iter() {
+            let id = ids[key.as_str()].as_str();
+            let label = node.label.replace('"', "#quot;");
+            writeln!(output, "  {id}[\"{label}\"]").ok();
+
+            let style = node_style(&node.kind);
+            classes
+                .entry(style.class)
+                .or_insert((style.color, vec![]))
+                .1
+                .push(id);
+        }
+
+        for (from, to, kind) in self.edges.iter() {
+            let arrow = match kind {
+                PBXDiagramEdgeKind::Dependency(PBXDependencyKind::LinkedProduct) => "-.->|links|",
+                PBXDiagramEdgeKind::Dependency(_) => "-->",
+                PBXDiagramEdgeKind::Package | PBXDiagramEdgeKind::LinkedFramework => "-.->",
+            };
+            writeln!(
+                output,
+                "  {} {arrow} {}",
+                ids[from.as_str()],
+                ids[to.as_str()]
+            )
+            .ok();
+        }
+
+        for (class, (color, ids)) in classes {
+            writeln!(output, "  classDef {class} fill:{color}").ok();
+            writeln!(output, "  class {} {class}", ids.join(",")).ok();
+        }
+
+        let cycle_links = self
+            .edges
+            .iter()
+            .enumerate()
+            .filter(|(_, (from, to, _))| self.is_cycle_edge(from, to))
+            .map(|(i, _)| i.to_string())
+            .collect::<Vec<_>>();
+        if !cycle_links.is_empty() {
+            writeln!(
+                output,
+                "  linkStyle {} {CYCLE_MERMAID_STYLE}",
+                cycle_links.join(",")
+            )
+            .ok();
+        }
+
+        output
+    }
+
+    fn ids(&self) -> HashMap<&str, String> {
+        self.nodes
+            .keys()
+            .enumerate()
+            .map(|(i, key)| (key.as_str(), format!("n{i}")))
+            .collect()
+    }
+
+    fn add_node(&mut self, key: &str, label: &str, kind: PBXDiagramNodeKind) {
+        self.nodes
+            .entry(key.to_string())
+            .or_insert_with(|| PBXDiagramNode {
+                label: label.to_string(),
+                kind,
+            });
+    }
+
+    fn add_edge(&mut self, from: &str, to: &str, kind: PBXDiagramEdgeKind) {
+        self.edges.insert((from.to_string(), to.to_string(), kind));
+    }
+}
+
+/// Get platform of a raw target object
+fn target_platform(objects: &PBXObjectCollection, target: &PBXHashMap) -> PBXTargetPlatform {
+    let bclist = target
+        .get_string("buildConfigurationList")
+        .and_then(|id| objects.get::<XCConfigurationList, _>(id));
+    PBXTargetInfo::platform(bclist.as_ref(), objects)
+}
+
+fn node_style(kind: &PBXDiagramNodeKind) -> NodeStyle {
+    use PBXProductType::*;
+    let (class, shape, color) = match kind {
+        PBXDiagramNodeKind::Target(product_type) => match product_type {
+            Application
+            | WatchApp
+            | Watch2App
+            | Watch2AppContainer
+            | MessagesApplication
+            | OnDemandInstallCapableApplication => ("application", "box", "#a6cee3"),
+            Framework | StaticFramework | XcFramework => ("framework", "box3d", "#b2df8a"),
+            DynamicLibrary | StaticLibrary => ("library", "component", "#fdbf6f"),
+            UnitTestBundle | UiTestBundle | OcUnitTestBundle => ("test", "note", "#fb9a99"),
+            CommandLineTool => ("tool", "box", "#d9d9d9"),
+            t if t.file_extension() == Some("appex") => ("extension", "box", "#cab2d6"),
+            _ => ("target", "box", "#f0f0f0"),
+        },
+        PBXDiagramNodeKind::PackageProduct => ("package_product", "ellipse", "#ffffb3"),
+        PBXDiagramNodeKind::Package => ("package", "folder", "#ffed6f"),
+        PBXDiagramNodeKind::Framework => ("linked_framework", "ellipse", "#ffffff"),
+    };
+
+    NodeStyle {
+        class,
+        shape,
+        color,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbxproj::test_demo_file;
+
+    #[test]
+    fn package_edges() {
+        let project = test_demo_file!(demo1);
+        let diagram = project.dependency_diagram(None);
+        let dot = diagram.to_dot();
+
+        assert!(dot.contains("[label=\"Wordle\", shape=box, fillcolor=\"#a6cee3\"]"));
+        assert!(dot.contains("[label=\"Logging\", shape=ellipse"));
+        assert!(dot.contains("[label=\"swift-log.git\", shape=folder"));
+        assert_eq!(dot.matches(" -> ").count(), 2);
+
+        let mermaid = diagram.to_mermaid();
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("[\"Wordle\"]"));
+        assert!(mermaid.contains("classDef application fill:#a6cee3"));
+    }
+
+    #[test]
+    fn linked_frameworks() {
+        let project = test_demo_file!(demo4);
+        let diagram = project.dependency_diagram(None);
+        let label = |key: &str| diagram.nodes()[key].label.as_str();
+        let edges = diagram
+            .edges()
+            .iter()
+            .map(|(from, to, kind)| (label(from), label(to), *kind))
+            .collect::<Vec<_>>();
+
+        assert!(edges.contains(&(
+            "BackbaseCXPTests",
+            "libBackbaseCXP",
+            PBXDiagramEdgeKind::Dependency(PBXDependencyKind::Explicit)
+        )));
+        assert!(edges.contains(&(
+            "BackbaseCXPTests",
+            "UIKit.framework",
+            PBXDiagramEdgeKind::LinkedFramework
+        )));
+        assert!(!edges.iter().any(|(_, to, _)| *to == "libBackbaseCXP.a"));
+        assert!(diagram.to_mermaid().contains("-.->"));
+    }
+
+    #[test]
+    fn cyclic_project() {
+        let mut project = test_demo_file!(demo8);
+        let graph = project.dependency_graph();
+        let target_id = |name| match graph.get_target_node(name) {
+            Some(PBXDependencyNode::Target(id)) => id.clone(),
+            _ => unreachable!(),
+        };
+        let (test, tests) = (target_id("test"), target_id("testTests"));
+
+        let objects = project.objects_mut();
+        let dependency = PBXHashMap::from_iter([
+            ("isa".into(), PBXValue::Kind("PBXTargetDependency".into())),
+            ("target".into(), PBXValue::String(tests.clone())),
+        ]);
+        objects.insert("CYCLE".into(), dependency);
+        objects.update(&test, |target| {
+            target.0.insert("dependencies".into(), vec!["CYCLE"].into())
+        });
+
+        let diagram = project.dependency_diagram(Some(&PBXTargetPlatform::IOS));
+        assert_eq!(
+            diagram.cycle(),
+            &BTreeSet::from([(test.clone(), tests.clone()), (tests, test)])
+        );
+        assert_eq!(diagram.to_dot().matches("color=\"#e31a1c\"").count(), 2);
+
+        let mermaid = diagram.to_mermaid();
+        let link_style = mermaid.lines().last().unwrap();
+        assert!(link_style.starts_with("  linkStyle "));
+        assert_eq!(link_style.matches(',').count(), 2);
+    }
+
+    #[test]
+    fn platform_filter() {
+        let project = test_demo_file!(demo1);
+        let diagram = project.dependency_diagram(Some(&PBXTargetPlatform::IOS));
+        assert_eq!(diagram.nodes().len(), 3);
+
+        let diagram = project.dependency_diagram(Some(&PBXTargetPlatform::MacOS));
+        assert!(diagram.nodes().is_empty());
+        assert!(diagram.edges().is_empty());
+    }
+}
@@ -0,0 +1,421 @@
+mod export;
+pub use export::*;
+
+use crate::pbxproj::*;
+use anyhow::Result;
+use itertools::Itertools;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Node of a [`PBXDependencyGraph`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PBXDependencyNode {
+    /// [`PBXTarget`] by object id
+    Target(String),
+    /// Swift package product by [`XCRemoteSwiftPackageReference`] id and product name.
+    ///
+    /// package is None for local packages.
+    PackageProduct {
+        /// Package reference id
+        package: Option<String>,
+        /// Product name
+        product_name: String,
+    },
+}
+
+/// How a dependency between two nodes was discovered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PBXDependencyKind {
+    /// [`PBXTargetDependency`] listed in target dependencies
+    Explicit,
+    /// Product of another target linked in Frameworks build phase
+    LinkedProduct,
+    /// [`XCSwiftPackageProductDependency`] used by target
+    PackageProduct,
+}
+
+/// Dependency graph between targets and swift package products of a project.
+///
+/// Besides explicit [`PBXTargetDependency`] entries, targets implicitly depend on products of
+/// other targets linked in their Frameworks build phase and on their package product
+/// dependencies.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PBXDependencyGraph {
+    names: BTreeMap<PBXDependencyNode, String>,
+    edges: BTreeMap<PBXDependencyNode, BTreeMap<PBXDependencyNode, PBXDependencyKind>>,
+}
+
+impl PBXDependencyGraph {
+    /// Build dependency graph from objects.
+    ///
+    /// NOTE: raw objects are used instead of [`PBXTarget`], since resolving targets of a cyclic
+    /// graph never terminates.
+    pub fn new(objects: &PBXObjectCollection) -> Self {
+        let mut graph = Self::default();
+        let targets = objects
+            .iter()
+            .filter(|(_, v)| {
+                v.get_kind("isa")
+                    .map(|k| k.is_pbx_target())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+        let products = targets
+            .iter()
+            .flat_map(|(id, target)| Some((target.get_string("productReference")?, *id)))
+            .collect::<HashMap<_, _>>();
+
+        for (id, target) in targets.iter() {
+            let node = PBXDependencyNode::Target(id.to_string());
+            let name = target.get_string("name").unwrap_or(id);
+            graph.add_node(node.clone(), name);
+
+            for dependency in get_ids(target, "dependencies")
+                .into_iter()
+                .flat_map(|id| objects.get_object(id))
+            {
+                if let Some(target_id) = dependency_target(objects, dependency) {
+                    let dependency = PBXDependencyNode::Target(target_id.to_string());
+                    graph.add_edge(&node, dependency, PBXDependencyKind::Explicit);
+                }
+                if let Some(product) = dependency.get_string("productRef") {
+                    graph.add_package_edge(objects, &node, product);
+                }
+            }
+
+            for build_file in get_ids(target, "buildPhases")
+                .into_iter()
+                .flat_map(|id| objects.get_object(id))
+                .filter(|phase| {
+                    phase
+                        .get_kind("isa")
+                        .and_then(|k| k.as_pbx_build_phase())
+                        .map(|k| k.is_frameworks())
+                        .unwrap_or_default()
+                })
+                .flat_map(|phase| get_ids(phase, "files"))
+                .flat_map(|id| objects.get_object(id))
+            {
+                if let Some(target_id) = build_file
+                    .get_string("fileRef")
+                    .and_then(|file| products.get(file))
+                {
+                    let dependency = PBXDependencyNode::Target(target_id.to_string());
+                    graph.add_edge(&node, dependency, PBXDependencyKind::LinkedProduct);
+                }
+                if let Some(product) = build_file.get_string("productRef") {
+                    graph.add_package_edge(objects, &node, product);
+                }
+            }
+
+            for product in get_ids(target, "packageProductDependencies") {
+                graph.add_package_edge(objects, &node, product);
+            }
+        }
+
+        graph
+    }
+
+    /// Get all nodes in the graph
+    pub fn nodes(&self) -> impl Iterator<Item = &PBXDependencyNode> {
+        self.edges.keys()
+    }
+
+    /// Get node display name, i.e. target name or package product name.
+    pub fn name(&self, node: &PBXDependencyNode) -> Option<&str> {
+        self.names.get(node).map(String::as_str)
+    }
+
+    /// Get node of a target with given name
+    pub fn get_target_node(&self, name: &str) -> Option<&PBXDependencyNode> {
+        self.names.iter().find_map(|(node, node_name)| {
+            (matches!(node, PBXDependencyNode::Target(_)) && node_name == name).then_some(node)
+        })
+    }
+
+    /// Get direct dependencies of a given node
+    pub fn dependencies(
+        &self,
+        node: &PBXDependencyNode,
+    ) -> Vec<(&PBXDependencyNode, PBXDependencyKind)> {
+        self.edges
+            .get(node)
+            .map(|edges| edges.iter().map(|(n, k)| (n, *k)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Get all transitive dependencies of a given node, dependencies first.
+    pub fn transitive_dependencies(&self, node: &PBXDependencyNode) -> Vec<&PBXDependencyNode> {
+        let mut visited = BTreeSet::new();
+        let mut output = vec![];
+        if let Some((node, _)) = self.edges.get_key_value(node) {
+            visited.insert(node);
+            self.visit(node, &mut visited, &mut output);
+        }
+        output
+    }
+
+    /// Find a dependency cycle, returned as a path starting and ending with the same node.
+    pub fn find_cycle(&self) -> Option<Vec<&PBXDependencyNode>> {
+        let mut done = BTreeSet::new();
+        let mut path = vec![];
+        self.nodes()
+            .find_map(|node| self.find_cycle_from(node, &mut done, &mut path))
+    }
+
+    /// Get topological build order of all nodes, dependencies first.
+    ///
+    /// Errors with cycle path if graph contains a cycle.
+    pub fn build_order(&self) -> Result<Vec<&PBXDependencyNode>> {
+        Ok(self.build_levels()?.into_iter().flatten().collect())
+    }
+
+    /// Get nodes grouped by levels, where each level only depends on previous levels, and
+    /// hence its nodes can be built in parallel.
+    ///
+    /// Errors with cycle path if graph contains a cycle.
+    pub fn build_levels(&self) -> Result<Vec<Vec<&PBXDependencyNode>>> {
+        if let Some(cycle) = self.find_cycle() {
+            anyhow::bail!(
+                "Dependency cycle: {}",
+                cycle
+                    .iter()
+                    .map(|n| self.name(n).unwrap_or_default())
+                    .join(" -> ")
+            )
+        }
+
+        let mut built = BTreeSet::new();
+        let mut levels = vec![];
+
+        while built.len() < self.edges.len() {
+            let level = self
+                .edges
+                .iter()
+                .filter(|(node, edges)| {
+                    !built.contains(node) && edges.keys().all(|n| built.contains(n))
+                })
+                .map(|(node, _)| node)
+                .collect::<Vec<_>>();
+
+            built.extend(level.iter().copied());
+            levels.push(level);
+        }
+
+        Ok(levels)
+    }
+
+    fn add_node(&mut self, node: PBXDependencyNode, name: &str) {
+        self.names.insert(node.clone(), name.to_string());
+        self.edges.entry(node).or_default();
+    }
+
+    fn add_edge(
+        &mut self,
+        from: &PBXDependencyNode,
+        to: PBXDependencyNode,
+        kind: PBXDependencyKind,
+    ) {
+        if from == &to {
+            return;
+        }
+        self.edges.entry(to.clone()).or_default();
+        self.edges
+            .entry(from.clone())
+            .or_default()
+            .entry(to)
+            .or_insert(kind);
+    }
+
+    fn add_package_edge(
+        &mut self,
+        objects: &PBXObjectCollection,
+        from: &PBXDependencyNode,
+        id: &str,
+    ) {
+        let product = match objects.get_object(id) {
+            Some(product) => product,
+            None => return,
+        };
+        let product_name = match product.get_string("productName") {
+            Some(name) => name,
+            None => return,
+        };
+        let node = PBXDependencyNode::PackageProduct {
+            package: product.get_string("package").cloned(),
+            product_name: product_name.clone(),
+        };
+
+        self.add_node(node.clone(), product_name);
+        self.add_edge(from, node, PBXDependencyKind::PackageProduct);
+    }
+
+    fn visit<'a>(
+        &'a self,
+        node: &'a PBXDependencyNode,
+        visited: &mut BTreeSet<&'a PBXDependencyNode>,
+        output: &mut Vec<&'a PBXDependencyNode>,
+    ) {
+        for dependency in self.edges[node].keys() {
+            if visited.insert(dependency) {
+                self.visit(dependency, visited, output);
+                output.push(dependency);
+            }
+        }
+    }
+
+    fn find_cycle_from<'a>(
+        &'a self,
+        node: &'a PBXDependencyNode,
+        done: &mut BTreeSet<&'a PBXDependencyNode>,
+        path: &mut Vec<&'a PBXDependencyNode>,
+    ) -> Option<Vec<&'a PBXDependencyNode>> {
+        if let Some(start) = path.iter().position(|n| *n == node) {
+            let mut cycle = path[start..].to_vec();
+            cycle.push(node);
+            return Some(cycle);
+        }
+        if done.contains(node) {
+            return None;
+        }
+
+        path.push(node);
+        for dependency in self.edges[node].keys() {
+            if let Some(cycle) = self.find_cycle_from(dependency, done, path) {
+                return Some(cycle);
+            }
+        }
+        path.pop();
+        done.insert(node);
+
+        None
+    }
+}
+
+/// Get object ids listed under a given key
+fn get_ids<'a>(value: &'a PBXHashMap, key: &str) -> Vec<&'a String> {
+    value
+        .get_vec(key)
+        .map(|v| v.as_vec_strings())
+        .unwrap_or_default()
+}
+
+/// Get id of target a PBXTargetDependency points to, falling back to its target proxy.
+fn dependency_target<'a>(
+    objects: &'a PBXObjectCollection,
+    dependency: &'a PBXHashMap,
+) -> Option<&'a String> {
+    dependency.get_string("target").or_else(|| {
+        let proxy = objects.get_object(dependency.get_string("targetProxy")?)?;
+        let id = proxy.get_string("remoteGlobalIDString")?;
+        objects
+            .get_object(id)?
+            .get_kind("isa")?
+            .is_pbx_target()
+            .then_some(id)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbxproj::test_demo_file;
+
+    #[test]
+    fn explicit_and_implicit_dependencies() {
+        let project = test_demo_file!(demo4);
+        let graph = project.dependency_graph();
+        let tests = graph.get_target_node("BackbaseCXPTests").unwrap();
+        let library = graph.get_target_node("libBackbaseCXP").unwrap();
+
+        assert!(graph
+            .dependencies(tests)
+            .contains(&(library, PBXDependencyKind::Explicit)));
+        assert_eq!(
+            graph
+                .transitive_dependencies(tests)
+                .into_iter()
+                .flat_map(|n| graph.name(n))
+                .collect::<Vec<_>>(),
+            vec!["BackbaseCXPAssets", "libBackbaseCXP"]
+        );
+
+        let order = graph.build_order().unwrap();
+        let position = |node| order.iter().position(|n| *n == node).unwrap();
+        assert!(position(library) < position(tests));
+        assert!(graph.find_cycle().is_none());
+    }
+
+    #[test]
+    fn linked_product_dependencies() {
+        let mut project = test_demo_file!(demo4);
+        let tests = match project
+            .dependency_graph()
+            .get_target_node("BackbaseCXPTests")
+        {
+            Some(PBXDependencyNode::Target(id)) => id.clone(),
+            _ => unreachable!(),
+        };
+        project
+            .objects_mut()
+            .update(&tests, |target| target.remove_value("dependencies"));
+
+        let graph = project.dependency_graph();
+        let dependencies = graph
+            .dependencies(graph.get_target_node("BackbaseCXPTests").unwrap())
+            .into_iter()
+            .map(|(n, k)| (graph.name(n).unwrap(), k))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            dependencies,
+            vec![("libBackbaseCXP", PBXDependencyKind::LinkedProduct)]
+        );
+    }
+
+    #[test]
+    fn package_product_dependencies() {
+        let project = test_demo_file!(demo1);
+        let graph = project.dependency_graph();
+        let wordle = graph.get_target_node("Wordle").unwrap();
+        let dependencies = graph.dependencies(wordle);
+
+        assert_eq!(dependencies.len(), 1);
+        assert_eq!(graph.name(dependencies[0].0), Some("Logging"));
+        assert_eq!(dependencies[0].1, PBXDependencyKind::PackageProduct);
+        assert_eq!(
+            graph.build_levels().unwrap(),
+            vec![vec![dependencies[0].0], vec![wordle]]
+        );
+    }
+
+    #[test]
+    fn cycle_detection() {
+        let mut project = test_demo_file!(demo8);
+        let graph = project.dependency_graph();
+        let target_id = |name| match graph.get_target_node(name) {
+            Some(PBXDependencyNode::Target(id)) => id.clone(),
+            _ => unreachable!(),
+        };
+        let (test, tests) = (target_id("test"), target_id("testTests"));
+
+        let objects = project.objects_mut();
+        let dependency = PBXHashMap::from_iter([
+            ("isa".into(), PBXValue::Kind("PBXTargetDependency".into())),
+            ("target".into(), PBXValue::String(tests)),
+        ]);
+        objects.insert("CYCLE".into(), dependency);
+        objects.update(&test, |target| {
+            target.0.insert("dependencies".into(), vec!["CYCLE"].into())
+        });
+
+        let graph = project.dependency_graph();
+        let cycle = graph.find_cycle().unwrap();
+        assert_eq!(cycle.len(), 3);
+        assert_eq!(cycle.first(), cycle.last());
+
+        let err = graph.build_order().unwrap_err().to_string();
+        assert!(
+            err == "Dependency cycle: test -> testTests -> test"
+                || err == "Dependency cycle: testTests -> test -> testTests"
+        );
+    }
+}
@@ -0,0 +1,310 @@
+//! Serde support for pbxproj values, in the shape of `plutil -convert json` output.
+//!
+//! OpenStep plists only have strings, so numbers, booleans and object kinds are serialized
+//! as strings. Deserialized JSON strings are kept as strings, except `isa` values which are
+//! object kinds, and only JSON numbers and booleans become numbers and booleans. Object views
+//! read numeric and flag values from their string spelling too.
+use super::*;
+use anyhow::Result;
+use serde::de::{self, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+impl Serialize for PBXValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::String(value) => serializer.serialize_str(value),
+            Self::Object(value) => value.serialize(serializer),
+            Self::Vec(value) => value.serialize(serializer),
+            Self::Number(value) => serializer.serialize_str(value.as_str()),
+            Self::Bool(value) => serializer.serialize_str(if *value { "YES" } else { "NO" }),
+            Self::Kind(value) => value.serialize(serializer),
+            Self::Null(()) => serializer.serialize_unit(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PBXValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(PBXValueVisitor)
+    }
+}
+
+struct PBXValueVisitor;
+
+impl<'de> Visitor<'de> for PBXValueVisitor {
+    type Value = PBXValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a pbxproj value")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<PBXValue, E> {
+        Ok(PBXValue::String(value.to_string()))
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<PBXValue, E> {
+        Ok(PBXValue::Bool(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<PBXValue, E> {
+        Ok(json_number(value))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<PBXValue, E> {
+        Ok(json_number(value))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<PBXValue, E> {
+        Ok(json_number(value))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<PBXValue, E> {
+        Ok(PBXValue::Null(()))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<PBXValue, E> {
+        Ok(PBXValue::Null(()))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<PBXValue, A::Error> {
+        PBXVecVisitor.visit_seq(seq).map(PBXValue::Vec)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<PBXValue, A::Error> {
+        PBXHashMapVisitor.visit_map(map).map(PBXValue::Object)
+    }
+}
+
+/// Number of a JSON number, falling back to string for lexemes pbxproj can't write unquoted,
+/// i.e. negative numbers.
+fn json_number<N: ToString>(value: N) -> PBXValue {
+    let lexeme = value.to_string();
+    match PBXNumber::new(lexeme.as_str()) {
+        Some(number) => PBXValue::Number(number),
+        None => PBXValue::String(lexeme),
+    }
+}
+
+impl Serialize for PBXHashMap {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (key, value) in self.iter() {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for PBXHashMap {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(PBXHashMapVisitor)
+    }
+}
+
+struct PBXHashMapVisitor;
+
+impl<'de> Visitor<'de> for PBXHashMapVisitor {
+    type Value = PBXHashMap;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a pbxproj object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<PBXHashMap, A::Error> {
+        let mut object = PBXHashMap::default();
+        while let Some((key, value)) = map.next_entry::<String, PBXValue>()? {
+            let value = match value {
+                PBXValue::String(isa) if key == "isa" => PBXValue::Kind(isa.as_str().into()),
+                value => value,
+            };
+            object.insert_value(key, value);
+        }
+        Ok(object)
+    }
+}
+
+impl Serialize for PBXVec {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for value in self.iter() {
+            seq.serialize_element(value)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for PBXVec {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(PBXVecVisitor)
+    }
+}
+
+struct PBXVecVisitor;
+
+impl<'de> Visitor<'de> for PBXVecVisitor {
+    type Value = PBXVec;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a pbxproj array")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<PBXVec, A::Error> {
+        let mut values = vec![];
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(PBXVec::new(values))
+    }
+}
+
+impl Serialize for PBXObjectKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PBXObjectKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let isa = String::deserialize(deserializer)?;
+        Ok(PBXObjectKind::from(isa.as_str()))
+    }
+}
+
+impl Serialize for PBXObjectCollection {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (id, object) in self.iter() {
+            map.serialize_entry(id, object)?;
+        }
+        map.end()
+    }
+}
+
+impl Serialize for PBXRootObject {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(5))?;
+        map.serialize_entry("archiveVersion", &self.archive_version().to_string())?;
+        map.serialize_entry("classes", self.classes())?;
+        map.serialize_entry("objectVersion", &self.object_version().to_string())?;
+        map.serialize_entry("objects", self.objects())?;
+        map.serialize_entry("rootObject", self.root_object_reference())?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for PBXRootObject {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        PBXHashMap::deserialize(deserializer)?
+            .try_into()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl PBXRootObject {
+    /// Serialize to JSON, matching `plutil -convert json` output
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Deserialize from JSON, i.e. output of [`PBXRootObject::to_json`] or `plutil`
+    pub fn from_json(content: &str) -> Result<Self> {
+        Ok(serde_json::from_str(content)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbxproj::test_demo_file;
+
+    #[test]
+    fn plutil_shape() {
+        let project = test_demo_file!(demo1);
+        let json = serde_json::to_value(&project).unwrap();
+
+        assert_eq!(json["archiveVersion"], "1");
+        assert_eq!(json["objectVersion"], "51");
+        assert_eq!(json["rootObject"], project.root_object_reference());
+
+        let target = project.get_target_by_name("Wordle").unwrap();
+        let target = &json["objects"][&target.id];
+        assert_eq!(target["isa"], "PBXNativeTarget");
+        assert_eq!(target["name"], "Wordle");
+        assert!(target["buildPhases"].is_array());
+
+        let settings = project
+            .values()
+            .find_map(|v| v.get_object("buildSettings"))
+            .unwrap();
+        let settings = serde_json::to_value(settings).unwrap();
+        assert_eq!(settings["ALWAYS_SEARCH_USER_PATHS"], "NO");
+        assert_eq!(settings["CLANG_CXX_LANGUAGE_STANDARD"], "gnu++14");
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let project = test_demo_file!(demo4);
+        let json = project.to_json().unwrap();
+        let imported = PBXRootObject::from_json(&json).unwrap();
+
+        assert_eq!(imported.objects().len(), project.objects().len());
+        assert_eq!(imported.object_version(), project.object_version());
+        for (id, object) in project.iter() {
+            let object = serde_json::to_value(object).unwrap();
+            assert_eq!(
+                serde_json::to_value(imported.get_object(id)).unwrap(),
+                object
+            );
+        }
+        assert_eq!(imported.to_json().unwrap(), json);
+
+        // Views read numbers and flags from their string spelling
+        assert_eq!(
+            imported.get_target_by_name("BackbaseCXP").unwrap().id,
+            project.get_target_by_name("BackbaseCXP").unwrap().id
+        );
+        assert_eq!(imported.targets().len(), project.targets().len());
+        assert_eq!(imported.files().len(), project.files().len());
+        let list = |p: &PBXRootObject| {
+            p.root_project()
+                .build_configuration_list
+                .default_configuration_is_visible
+        };
+        assert_eq!(list(&imported), list(&project));
+        let file_encodings = |p: &PBXRootObject| {
+            p.files()
+                .into_iter()
+                .map(|f| f.file_encoding)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(file_encodings(&imported), file_encodings(&project));
+    }
+
+    #[test]
+    fn values() {
+        let value: PBXValue = serde_json::from_str(
+            r#"{"isa": "PBXGroup", "a": ["YES", "007", 1, 1.5, -1, true, "PBXGroup", null], "b": "2024"}"#,
+        )
+        .unwrap();
+        let object = value.as_object().unwrap();
+        let list = object.get_vec("a").unwrap();
+
+        assert!(object.get_kind("isa").unwrap().is_pbx_fsreference());
+        // JSON strings stay strings
+        assert_eq!(list[0], PBXValue::String("YES".into()));
+        assert_eq!(list[1], PBXValue::String("007".into()));
+        assert_eq!(list[2].as_number().unwrap(), &1);
+        assert_eq!(list[3].as_number().unwrap().as_str(), "1.5");
+        assert_eq!(list[4], PBXValue::String("-1".into()));
+        assert_eq!(list[5], PBXValue::Bool(true));
+        assert_eq!(list[6], PBXValue::String("PBXGroup".into()));
+        assert_eq!(list[7], PBXValue::Null(()));
+        assert_eq!(object.get_string("b").unwrap(), "2024");
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#"{"isa":"PBXGroup","a":["YES","007","1","1.5","-1","YES","PBXGroup",null],"b":"2024"}"#
+        );
+    }
+}
@@ -1,8 +1,15 @@
 //! pbxproj file serialize and deserializer
+mod edit;
+mod escape;
+mod graph;
+mod json;
 mod object;
 mod value;
 
+pub(crate) mod parser;
 pub(crate) mod pest;
+pub use escape::*;
+pub use graph::*;
 pub use object::*;
 pub use value::*;
 
@@ -55,7 +62,7 @@ impl PBXRootObject {
     }
 
     /// Get Root PBXProject
-    pub fn root_project(&self) -> PBXProject {
+    pub fn root_project(&self) -> PBXProject<'_> {
         self.objects
             .projects()
             .into_iter()
@@ -64,7 +71,7 @@ impl PBXRootObject {
     }
 
     /// Get root group
-    pub fn root_group(&self) -> PBXFSReference {
+    pub fn root_group(&self) -> PBXFSReference<'_> {
         self.root_project().main_group
     }
 
@@ -80,6 +87,16 @@ impl PBXRootObject {
         &mut self.objects
     }
 
+    /// Get dependency graph of targets and swift package products
+    pub fn dependency_graph(&self) -> PBXDependencyGraph {
+        PBXDependencyGraph::new(&self.objects)
+    }
+
+    /// Get renderable dependency diagram, only including targets of a given platform if any.
+    pub fn dependency_diagram(&self, platform: Option<&PBXTargetPlatform>) -> PBXDependencyDiagram {
+        PBXDependencyDiagram::new(&self.objects, platform)
+    }
+
     /// Get a hashmap of targets and their information
     pub fn targets_info(&self) -> HashMap<String, PBXTargetInfo> {
         self.targets()
@@ -107,11 +124,11 @@ impl std::fmt::Debug for PBXRootObject {
 impl TryFrom<PBXHashMap> for PBXRootObject {
     type Error = anyhow::Error;
     fn try_from(mut map: PBXHashMap) -> Result<Self> {
-        let archive_version = map.try_remove_number("archiveVersion")? as u8;
-        let object_version = map.try_remove_number("objectVersion")? as u8;
+        let archive_version = map.try_get_str("archiveVersion")?.parse()?;
+        let object_version = map.try_get_str("objectVersion")?.parse()?;
         let classes = map.try_remove_object("classes").unwrap_or_default();
         let root_object_reference = map.try_remove_string("rootObject")?;
-        let objects = PBXObjectCollection(
+        let objects = PBXObjectCollection::new(
             map.try_remove_object("objects")?
                 .0
                 .into_iter()
@@ -132,9 +149,12 @@ impl TryFrom<PBXHashMap> for PBXRootObject {
 impl TryFrom<&str> for PBXRootObject {
     type Error = anyhow::Error;
     fn try_from(content: &str) -> Result<Self> {
-        use crate::pbxproj::pest::PBXProjectParser;
+        #[cfg(not(feature = "pest_parser"))]
+        use crate::pbxproj::parser::PBXParser as Parser;
+        #[cfg(feature = "pest_parser")]
+        use crate::pbxproj::pest::PBXProjectParser as Parser;
 
-        PBXProjectParser::try_from_str(content)?.pipe(Self::try_from)
+        Parser::try_from_str(content)?.pipe(Self::try_from)
     }
 }
 
@@ -149,7 +169,7 @@ impl TryFrom<&Path> for PBXRootObject {
     type Error = anyhow::Error;
 
     fn try_from(value: &Path) -> Result<Self> {
-        std::fs::read_to_string(&value)
+        std::fs::read_to_string(value)
             .map_err(|e| anyhow::anyhow!("PBXProjectData from path {value:?}: {e}"))?
             .pipe(TryFrom::try_from)
     }
@@ -262,8 +282,8 @@ macro_rules! test_demo_file {
         let (root, name) = (env!("CARGO_MANIFEST_DIR"), stringify!($name));
         let path = format!("{root}/tests/samples/{name}.pbxproj");
         let file = crate::pbxproj::PBXRootObject::try_from(std::path::PathBuf::from(path));
-        if file.is_err() {
-            println!("Error: {:#?}", file.as_ref().unwrap_err())
+        if let Err(err) = &file {
+            println!("Error: {err:#?}")
         }
         assert!(file.is_ok());
         file.unwrap()
@@ -29,14 +29,18 @@ impl<'a> AsPBXObject<'a> for XCConfigurationList<'a> {
                 .get_vec("buildConfigurations")
                 .map(|v| objects.get_vec(v.as_vec_strings()))
                 .unwrap_or_default(),
-            default_configuration_is_visible: value
-                .try_get_number("defaultConfigurationIsVisible")?
-                == &1,
+            default_configuration_is_visible: value.try_get_str("defaultConfigurationIsVisible")?
+                == "1",
             default_configuration_name: value.get_string("defaultConfigurationName"),
         })
     }
 }
 impl<'a> XCConfigurationList<'a> {
+    /// Returns the build configuration with the given name (if it exists)
+    pub fn get_configuration_by_name(&self, name: &str) -> Option<&XCBuildConfiguration<'a>> {
+        self.build_configurations.iter().find(|c| c.name == name)
+    }
+
     /// Extract SDKROOT from build configurations
     pub fn extract_sdkroot_from_children(&self, objects: &PBXObjectCollection) -> Option<String> {
         let mut sdkroots = self
@@ -0,0 +1,263 @@
+use crate::pbxproj::*;
+use std::path::{Path, PathBuf};
+
+/// Locations Xcode derives build directory settings, i.e. `BUILT_PRODUCTS_DIR`, from.
+///
+/// Directories follow the layout of Xcode derived data: products are built to
+/// `<build_dir>/Products/<CONFIGURATION><EFFECTIVE_PLATFORM_NAME>` and intermediates to
+/// `<build_dir>/Intermediates.noindex/<PROJECT_NAME>.build`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XCBuildLocations {
+    /// Project name, i.e. `App` for `App.xcodeproj`
+    pub project_name: String,
+    /// Build directory, i.e. `DerivedData/App-<hash>/Build`
+    pub build_dir: PathBuf,
+}
+
+impl XCBuildLocations {
+    /// Create new build locations for a given project name and build directory
+    pub fn new<S: Into<String>, P: AsRef<Path>>(project_name: S, build_dir: P) -> Self {
+        Self {
+            project_name: project_name.into(),
+            build_dir: build_dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Build settings derived from locations.
+    ///
+    /// Values refer to each other as well as to `CONFIGURATION`, `TARGET_NAME` and
+    /// `EFFECTIVE_PLATFORM_NAME`, and are only meaningful once expanded.
+    pub fn settings(&self) -> Vec<(&'static str, String)> {
+        let build_dir = self.build_dir.to_string_lossy();
+        [
+            ("ACTION", "build".to_string()),
+            ("PROJECT_NAME", self.project_name.clone()),
+            ("SYMROOT", format!("{build_dir}/Products")),
+            ("BUILD_DIR", "$(SYMROOT)".into()),
+            ("BUILD_ROOT", "$(SYMROOT)".into()),
+            ("OBJROOT", format!("{build_dir}/Intermediates.noindex")),
+            (
+                "PROJECT_TEMP_DIR",
+                "$(OBJROOT)/$(PROJECT_NAME).build".into(),
+            ),
+            (
+                "PROJECT_DERIVED_FILE_DIR",
+                "$(PROJECT_TEMP_DIR)/DerivedSources".into(),
+            ),
+            (
+                "CONFIGURATION_BUILD_DIR",
+                "$(BUILD_DIR)/$(CONFIGURATION)$(EFFECTIVE_PLATFORM_NAME)".into(),
+            ),
+            ("BUILT_PRODUCTS_DIR", "$(CONFIGURATION_BUILD_DIR)".into()),
+            ("TARGET_BUILD_DIR", "$(CONFIGURATION_BUILD_DIR)".into()),
+            (
+                "SHARED_DERIVED_FILE_DIR",
+                "$(BUILT_PRODUCTS_DIR)/DerivedSources".into(),
+            ),
+            (
+                "CONFIGURATION_TEMP_DIR",
+                "$(PROJECT_TEMP_DIR)/$(CONFIGURATION)$(EFFECTIVE_PLATFORM_NAME)".into(),
+            ),
+            (
+                "TARGET_TEMP_DIR",
+                "$(CONFIGURATION_TEMP_DIR)/$(TARGET_NAME).build".into(),
+            ),
+            ("TEMP_DIR", "$(TARGET_TEMP_DIR)".into()),
+            ("TEMP_FILES_DIR", "$(TARGET_TEMP_DIR)".into()),
+            (
+                "DERIVED_FILE_DIR",
+                "$(TARGET_TEMP_DIR)/DerivedSources".into(),
+            ),
+            ("DERIVED_FILES_DIR", "$(DERIVED_FILE_DIR)".into()),
+            ("DERIVED_SOURCES_DIR", "$(DERIVED_FILE_DIR)".into()),
+            ("OBJECT_FILE_DIR", "$(TARGET_TEMP_DIR)/Objects".into()),
+        ]
+        .into()
+    }
+}
+
+/// Product path settings Xcode derives from a given product type, i.e. `INFOPLIST_PATH` or
+/// `CODESIGNING_FOLDER_PATH`, relative to `TARGET_BUILD_DIR` unless noted otherwise.
+///
+/// Bundles are shallow, except on macOS where `is_deep` puts their contents in `Contents`, or
+/// `Versions/A` for frameworks. Products that aren't bundles, i.e. tools and libraries, only get
+/// executable and product names.
+pub(crate) fn product_settings(
+    product_type: &PBXProductType,
+    is_deep: bool,
+) -> Vec<(&'static str, String)> {
+    let is_wrapper = product_type.explicit_file_type().starts_with("wrapper.");
+    let extension = product_type.file_extension().unwrap_or_default();
+    let mut settings = vec![
+        (
+            "FULL_PRODUCT_NAME",
+            product_type.file_name("$(PRODUCT_NAME)"),
+        ),
+        (
+            "CODESIGNING_FOLDER_PATH",
+            "$(TARGET_BUILD_DIR)/$(FULL_PRODUCT_NAME)".into(),
+        ),
+    ];
+    if !is_wrapper {
+        settings.extend([
+            ("EXECUTABLE_NAME", "$(FULL_PRODUCT_NAME)".into()),
+            ("EXECUTABLE_PATH", "$(EXECUTABLE_NAME)".into()),
+        ]);
+        return settings;
+    }
+
+    let is_framework = matches!(
+        product_type,
+        PBXProductType::Framework | PBXProductType::StaticFramework
+    );
+    let (contents, executable_folder, resources) = match (is_deep, is_framework) {
+        (false, _) => (
+            "$(WRAPPER_NAME)",
+            "$(CONTENTS_FOLDER_PATH)",
+            "$(CONTENTS_FOLDER_PATH)",
+        ),
+        (true, true) => (
+            "$(WRAPPER_NAME)/Versions/A",
+            "$(CONTENTS_FOLDER_PATH)",
+            "$(CONTENTS_FOLDER_PATH)/Resources",
+        ),
+        (true, false) => (
+            "$(WRAPPER_NAME)/Contents",
+            "$(CONTENTS_FOLDER_PATH)/MacOS",
+            "$(CONTENTS_FOLDER_PATH)/Resources",
+        ),
+    };
+    let info_plist = match (is_deep, is_framework) {
+        (true, true) => "$(UNLOCALIZED_RESOURCES_FOLDER_PATH)/Info.plist",
+        _ => "$(CONTENTS_FOLDER_PATH)/Info.plist",
+    };
+    settings.extend([
+        ("WRAPPER_EXTENSION", extension.to_string()),
+        ("WRAPPER_SUFFIX", ".$(WRAPPER_EXTENSION)".into()),
+        ("WRAPPER_NAME", "$(PRODUCT_NAME)$(WRAPPER_SUFFIX)".into()),
+        ("CONTENTS_FOLDER_PATH", contents.into()),
+        ("EXECUTABLE_FOLDER_PATH", executable_folder.into()),
+        ("EXECUTABLE_NAME", "$(PRODUCT_NAME)".into()),
+        (
+            "EXECUTABLE_PATH",
+            "$(EXECUTABLE_FOLDER_PATH)/$(EXECUTABLE_NAME)".into(),
+        ),
+        ("UNLOCALIZED_RESOURCES_FOLDER_PATH", resources.into()),
+        ("INFOPLIST_PATH", info_plist.into()),
+        (
+            "FRAMEWORKS_FOLDER_PATH",
+            "$(CONTENTS_FOLDER_PATH)/Frameworks".into(),
+        ),
+        (
+            "PLUGINS_FOLDER_PATH",
+            "$(CONTENTS_FOLDER_PATH)/PlugIns".into(),
+        ),
+    ]);
+    settings
+}
+
+/// Get `EFFECTIVE_PLATFORM_NAME` of a given `SDKROOT`, i.e. `-iphonesimulator`.
+///
+/// Empty for macOS, as its products aren't suffixed with a platform.
+pub(crate) fn effective_platform_name(sdkroot: &str) -> String {
+    let name = Path::new(sdkroot)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(sdkroot);
+    let name = name
+        .strip_suffix(".sdk")
+        .unwrap_or(name)
+        .trim_end_matches(|c: char| c.is_ascii_digit() || c == '.')
+        .to_lowercase();
+
+    match name.as_str() {
+        "" | "macosx" => String::default(),
+        platform => format!("-{platform}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbxproj::test_demo_file;
+
+    #[test]
+    fn platform_names() {
+        assert_eq!(effective_platform_name("iphoneos"), "-iphoneos");
+        assert_eq!(effective_platform_name("macosx"), "");
+        assert_eq!(
+            effective_platform_name("/Applications/Xcode.app/iPhoneSimulator17.2.sdk"),
+            "-iphonesimulator"
+        );
+    }
+
+    #[test]
+    fn build_locations() {
+        let project = test_demo_file!(demo2);
+        let target = project
+            .get_target_by_name("backbase-showcase-mobile-ios")
+            .unwrap();
+        let locations = XCBuildLocations::new("Showcase", "/derived/Build");
+        let settings = target
+            .build_settings_with_locations(&project, "Debug", "/root", &locations)
+            .unwrap();
+        let get = |key: &str| settings.get(key).unwrap().as_str();
+
+        assert_eq!(get("PROJECT_NAME"), "Showcase");
+        assert_eq!(
+            get("BUILT_PRODUCTS_DIR"),
+            "/derived/Build/Products/Debug-iphoneos"
+        );
+        assert_eq!(get("TARGET_BUILD_DIR"), get("BUILT_PRODUCTS_DIR"));
+        assert_eq!(
+            get("DERIVED_FILE_DIR"),
+            "/derived/Build/Intermediates.noindex/Showcase.build/Debug-iphoneos/\
+            backbase-showcase-mobile-ios.build/DerivedSources"
+        );
+        assert_eq!(get("WRAPPER_NAME"), "Backbase.app");
+        assert_eq!(get("INFOPLIST_PATH"), "Backbase.app/Info.plist");
+        assert_eq!(
+            settings
+                .try_expand("$(TARGET_BUILD_DIR)/$(INFOPLIST_PATH)")
+                .unwrap(),
+            "/derived/Build/Products/Debug-iphoneos/Backbase.app/Info.plist"
+        );
+        assert_eq!(
+            get("CODESIGNING_FOLDER_PATH"),
+            "/derived/Build/Products/Debug-iphoneos/Backbase.app"
+        );
+    }
+
+    #[test]
+    fn product_paths() {
+        let settings = |product_type: PBXProductType, is_deep: bool| {
+            let mut settings = XCBuildSettings::default();
+            settings.insert("PRODUCT_NAME", "Kit");
+            settings.insert("TARGET_BUILD_DIR", "/products");
+            for (key, value) in product_settings(&product_type, is_deep) {
+                settings.insert(key, value);
+            }
+            settings.expanded()
+        };
+
+        let framework = settings(PBXProductType::Framework, false);
+        assert_eq!(framework["INFOPLIST_PATH"], "Kit.framework/Info.plist");
+        assert_eq!(framework["EXECUTABLE_PATH"], "Kit.framework/Kit");
+
+        let framework = settings(PBXProductType::Framework, true);
+        assert_eq!(
+            framework["INFOPLIST_PATH"],
+            "Kit.framework/Versions/A/Resources/Info.plist"
+        );
+
+        let app = settings(PBXProductType::Application, true);
+        assert_eq!(app["INFOPLIST_PATH"], "Kit.app/Contents/Info.plist");
+        assert_eq!(app["EXECUTABLE_PATH"], "Kit.app/Contents/MacOS/Kit");
+        assert_eq!(app["FRAMEWORKS_FOLDER_PATH"], "Kit.app/Contents/Frameworks");
+
+        let library = settings(PBXProductType::StaticLibrary, false);
+        assert_eq!(library["EXECUTABLE_NAME"], "libKit.a");
+        assert_eq!(library["CODESIGNING_FOLDER_PATH"], "/products/libKit.a");
+        assert!(!library.contains_key("INFOPLIST_PATH"));
+    }
+}
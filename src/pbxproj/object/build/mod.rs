@@ -1,7 +1,11 @@
 mod config;
 mod list;
+mod locations;
 mod phase;
+mod settings;
 
 pub use config::*;
 pub use list::*;
+pub use locations::*;
 pub use phase::*;
+pub use settings::*;
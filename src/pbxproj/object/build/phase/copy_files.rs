@@ -0,0 +1,119 @@
+use crate::pbxproj::*;
+
+/// [`PBXBuildPhase`] destination of copied files (`dstSubfolderSpec`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PBXCopyFilesDestination {
+    /// Absolute path
+    Absolute,
+    /// Product bundle
+    Wrapper,
+    /// Executables directory
+    Executables,
+    /// Resources directory
+    Resources,
+    /// Frameworks directory
+    Frameworks,
+    /// Shared frameworks directory
+    SharedFrameworks,
+    /// Shared support directory
+    SharedSupport,
+    /// Plug-ins directory, used for app extensions
+    PlugIns,
+    /// Java resources directory
+    JavaResources,
+    /// Products directory, used with a subpath for XPC services or watch apps
+    Products,
+    /// Other, unknown `dstSubfolderSpec`
+    Other(u8),
+}
+
+impl PBXCopyFilesDestination {
+    /// Get `dstSubfolderSpec` value
+    pub fn spec(&self) -> u8 {
+        match self {
+            Self::Absolute => 0,
+            Self::Wrapper => 1,
+            Self::Executables => 6,
+            Self::Resources => 7,
+            Self::Frameworks => 10,
+            Self::SharedFrameworks => 11,
+            Self::SharedSupport => 12,
+            Self::PlugIns => 13,
+            Self::JavaResources => 15,
+            Self::Products => 16,
+            Self::Other(spec) => *spec,
+        }
+    }
+}
+
+impl From<u8> for PBXCopyFilesDestination {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Absolute,
+            1 => Self::Wrapper,
+            6 => Self::Executables,
+            7 => Self::Resources,
+            10 => Self::Frameworks,
+            11 => Self::SharedFrameworks,
+            12 => Self::SharedSupport,
+            13 => Self::PlugIns,
+            15 => Self::JavaResources,
+            16 => Self::Products,
+            spec => Self::Other(spec),
+        }
+    }
+}
+
+impl TryFrom<&PBXValue> for PBXCopyFilesDestination {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &PBXValue) -> Result<Self, Self::Error> {
+        value
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("dstSubfolderSpec is not a number"))?
+            .parse::<u8>()
+            .map(Into::into)
+            .map_err(|e| anyhow::anyhow!("dstSubfolderSpec is not a number: {e}"))
+    }
+}
+
+impl From<PBXCopyFilesDestination> for PBXValue {
+    fn from(value: PBXCopyFilesDestination) -> Self {
+        PBXValue::from(value.spec() as isize)
+    }
+}
+
+/// Product of another target copied by a [`PBXBuildPhase`] of kind `CopyFiles`, i.e. an
+/// embedded framework, app extension or watch app.
+#[derive(Debug)]
+pub struct PBXEmbeddedProduct<'a> {
+    /// Copy destination
+    pub destination: PBXCopyFilesDestination,
+    /// Path relative to destination
+    pub subpath: Option<&'a String>,
+    /// Target producing the copied file
+    pub target: PBXTarget<'a>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spec_round_trip() {
+        for spec in 0..=20u8 {
+            let destination = PBXCopyFilesDestination::from(spec);
+            assert_eq!(destination.spec(), spec);
+            let value = PBXValue::from(destination);
+            assert_eq!(
+                PBXCopyFilesDestination::try_from(&value).unwrap(),
+                destination
+            );
+        }
+        assert_eq!(
+            PBXCopyFilesDestination::from(2),
+            PBXCopyFilesDestination::Other(2)
+        );
+        assert!(PBXCopyFilesDestination::try_from(&PBXValue::from("Frameworks")).is_err());
+    }
+}
@@ -0,0 +1,232 @@
+use crate::pbxproj::*;
+use std::{convert::Infallible, fmt::Display, str::FromStr};
+
+/// [`PBXBuildFile`] attribute, i.e. header visibility or linking mode
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PBXBuildFileAttribute {
+    /// Public header
+    Public,
+    /// Private header
+    Private,
+    /// Weakly linked framework
+    Weak,
+    /// Code sign copied file
+    CodeSignOnCopy,
+    /// Remove headers from copied framework
+    RemoveHeadersOnCopy,
+    /// No code generation for a model or asset file (`no_codegen`)
+    NoCodegen,
+    /// Other attribute, i.e. `Required`
+    Other(String),
+}
+
+impl PBXBuildFileAttribute {
+    /// Get attribute as it is written in pbxproj file
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Public => "Public",
+            Self::Private => "Private",
+            Self::Weak => "Weak",
+            Self::CodeSignOnCopy => "CodeSignOnCopy",
+            Self::RemoveHeadersOnCopy => "RemoveHeadersOnCopy",
+            Self::NoCodegen => "no_codegen",
+            Self::Other(other) => other,
+        }
+    }
+}
+
+impl FromStr for PBXBuildFileAttribute {
+    type Err = Infallible;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(match value {
+            "Public" => Self::Public,
+            "Private" => Self::Private,
+            "Weak" => Self::Weak,
+            "CodeSignOnCopy" => Self::CodeSignOnCopy,
+            "RemoveHeadersOnCopy" => Self::RemoveHeadersOnCopy,
+            "no_codegen" => Self::NoCodegen,
+            other => Self::Other(other.to_string()),
+        })
+    }
+}
+
+impl Display for PBXBuildFileAttribute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Typed [`PBXBuildFile`] settings
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PBXBuildFileSettings {
+    /// Attributes (`ATTRIBUTES`)
+    pub attributes: Vec<PBXBuildFileAttribute>,
+    /// Per file compiler flags (`COMPILER_FLAGS`), split like a shell would
+    pub compiler_flags: Vec<String>,
+    /// Keys that aren't modeled
+    pub extra: PBXHashMap,
+}
+
+impl PBXBuildFileSettings {
+    /// Whether settings include a given attribute
+    pub fn has_attribute(&self, attribute: &PBXBuildFileAttribute) -> bool {
+        self.attributes.contains(attribute)
+    }
+
+    /// Add or remove a given attribute
+    pub fn set_attribute(&mut self, attribute: PBXBuildFileAttribute, enabled: bool) {
+        if !enabled {
+            self.attributes.retain(|a| a != &attribute);
+        } else if !self.has_attribute(&attribute) {
+            self.attributes.push(attribute);
+        }
+    }
+
+    /// Set header visibility, replacing `Public` or `Private` attribute
+    pub fn set_header_visibility(&mut self, visibility: PBXHeaderVisibility) {
+        self.attributes.retain(|a| {
+            !matches!(
+                a,
+                PBXBuildFileAttribute::Public | PBXBuildFileAttribute::Private
+            )
+        });
+        match visibility {
+            PBXHeaderVisibility::Public => self.attributes.push(PBXBuildFileAttribute::Public),
+            PBXHeaderVisibility::Private => self.attributes.push(PBXBuildFileAttribute::Private),
+            PBXHeaderVisibility::Project => {}
+        }
+    }
+
+    /// Set compiler flags
+    pub fn set_compiler_flags<I, S>(&mut self, flags: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.compiler_flags = flags.into_iter().map(Into::into).collect();
+    }
+
+    /// Whether there is no setting at all
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty() && self.compiler_flags.is_empty() && self.extra.is_empty()
+    }
+}
+
+impl From<&PBXHashMap> for PBXBuildFileSettings {
+    fn from(value: &PBXHashMap) -> Self {
+        let mut extra = value.clone();
+        let attributes = extra
+            .remove_vec("ATTRIBUTES")
+            .map(|vec| {
+                vec.as_vec_strings()
+                    .into_iter()
+                    .map(|a| a.parse().unwrap())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let compiler_flags = extra
+            .remove_string("COMPILER_FLAGS")
+            .map(|flags| split_setting(&flags))
+            .unwrap_or_default();
+
+        Self {
+            attributes,
+            compiler_flags,
+            extra,
+        }
+    }
+}
+
+impl From<&PBXValue> for PBXBuildFileSettings {
+    fn from(value: &PBXValue) -> Self {
+        value.as_object().map(Into::into).unwrap_or_default()
+    }
+}
+
+impl From<PBXBuildFileSettings> for PBXHashMap {
+    fn from(value: PBXBuildFileSettings) -> Self {
+        let mut map = value.extra;
+        if !value.attributes.is_empty() {
+            let attributes = value
+                .attributes
+                .iter()
+                .map(|a| PBXValue::from(a.as_str()))
+                .collect::<Vec<_>>();
+            map.insert_value("ATTRIBUTES", PBXValue::Vec(PBXVec::new(attributes)));
+        }
+        if !value.compiler_flags.is_empty() {
+            let flags = shlex::try_join(value.compiler_flags.iter().map(String::as_str))
+                .unwrap_or_else(|_| value.compiler_flags.join(" "));
+            map.insert_value("COMPILER_FLAGS", flags);
+        }
+        map
+    }
+}
+
+impl<'a> PBXBuildFile<'a> {
+    /// Get typed settings
+    pub fn typed_settings(&self) -> PBXBuildFileSettings {
+        self.settings.map(Into::into).unwrap_or_default()
+    }
+
+    /// Get attributes, i.e. `Public` for a public header
+    pub fn attributes(&self) -> Vec<PBXBuildFileAttribute> {
+        self.typed_settings().attributes
+    }
+
+    /// Get compiler flags, i.e. `-fno-objc-arc`
+    pub fn compiler_flags(&self) -> Vec<String> {
+        self.typed_settings().compiler_flags
+    }
+
+    /// Whether file has a given attribute
+    pub fn has_attribute(&self, attribute: &PBXBuildFileAttribute) -> bool {
+        self.typed_settings().has_attribute(attribute)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbxproj::test_demo_file;
+
+    #[test]
+    fn read_settings() {
+        let project = test_demo_file!(demo8);
+        let files = project.build_files();
+        let file = |id: &str| files.iter().find(|f| f.id == id).unwrap();
+
+        assert!(file("4FB900851D5B317C0074397B").has_attribute(&PBXBuildFileAttribute::Weak));
+        assert_eq!(
+            file("4FB900671D5B314C0074397B").compiler_flags(),
+            vec!["-flag", "-flag2"]
+        );
+    }
+
+    #[test]
+    fn write_settings() {
+        let mut settings = PBXBuildFileSettings::default();
+        settings.set_attribute(PBXBuildFileAttribute::Weak, true);
+        settings.set_attribute(PBXBuildFileAttribute::Weak, true);
+        settings.set_header_visibility(PBXHeaderVisibility::Private);
+        settings.set_header_visibility(PBXHeaderVisibility::Public);
+        settings.set_compiler_flags(["-fno-objc-arc", "-DNAME=a b"]);
+
+        let map = PBXHashMap::from(settings.clone());
+        assert_eq!(
+            map.get_vec("ATTRIBUTES").unwrap().as_vec_strings(),
+            vec!["Weak", "Public"]
+        );
+        assert_eq!(
+            map.get_str("COMPILER_FLAGS"),
+            Some("-fno-objc-arc '-DNAME=a b'")
+        );
+        assert_eq!(PBXBuildFileSettings::from(&map), settings);
+
+        settings.set_attribute(PBXBuildFileAttribute::Weak, false);
+        settings.set_header_visibility(PBXHeaderVisibility::Project);
+        settings.set_compiler_flags(Vec::<String>::new());
+        assert!(settings.is_empty());
+    }
+}
@@ -35,9 +35,9 @@ impl PBXBuildPhaseKind {
     }
 }
 
-impl ToString for PBXBuildPhaseKind {
-    fn to_string(&self) -> String {
-        match self {
+impl std::fmt::Display for PBXBuildPhaseKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
             Self::Sources => "Sources",
             Self::Frameworks => "Frameworks",
             Self::Resources => "Resources",
@@ -45,8 +45,8 @@ impl ToString for PBXBuildPhaseKind {
             Self::RunScript => "Run Script",
             Self::Headers => "Headers",
             Self::CarbonResources => "Rez",
-        }
-        .into()
+        };
+        write!(f, "{value}")
     }
 }
 
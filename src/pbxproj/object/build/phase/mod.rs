@@ -1,12 +1,20 @@
+mod copy_files;
 mod file;
+mod file_settings;
 mod kind;
 mod rule;
 mod script;
+mod script_paths;
+mod script_run;
 
+pub use copy_files::*;
 pub use file::*;
+pub use file_settings::*;
 pub use kind::*;
 pub use rule::*;
 pub use script::*;
+pub use script_paths::*;
+pub use script_run::*;
 
 use crate::pbxproj::*;
 
@@ -25,6 +33,10 @@ pub struct PBXBuildPhase<'a> {
     pub output_file_list_paths: Option<Vec<&'a String>>,
     /// Element run only for deployment post processing value.
     pub run_only_for_deployment_postprocessing: bool,
+    /// Copy destination (relevant only for `PBXCopyFilesBuildPhase`)
+    pub destination: Option<PBXCopyFilesDestination>,
+    /// Copy destination subpath (relevant only for `PBXCopyFilesBuildPhase`)
+    pub destination_subpath: Option<&'a String>,
     /// Build Phase Kind
     pub kind: PBXBuildPhaseKind,
     /// inner (Some if PBXBuildPhase is PBXShellScriptBuildPhase)
@@ -67,6 +79,12 @@ impl<'a> PBXBuildPhase<'a> {
     pub fn is_carbon_resources(&self) -> bool {
         self.kind.is_carbon_resources()
     }
+
+    /// Whether build phase only runs when installing, as Xcode labels
+    /// `runOnlyForDeploymentPostprocessing` for run script phases
+    pub fn run_only_when_installing(&self) -> bool {
+        self.run_only_for_deployment_postprocessing
+    }
 }
 
 impl<'a> AsPBXObject<'a> for PBXBuildPhase<'a> {
@@ -87,18 +105,16 @@ impl<'a> AsPBXObject<'a> for PBXBuildPhase<'a> {
         Ok(Self {
             id,
             build_action_mask: value
-                .try_get_number("buildActionMask")
-                .map(|v| v.clone())
-                .unwrap_or_else(|_| Self::DEFAULT_BUILD_ACTION_MASK),
+                .get_str("buildActionMask")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(Self::DEFAULT_BUILD_ACTION_MASK),
             files: value
                 .get_vec("files")
-                .and_then(|vec| {
-                    Some(
-                        vec.as_vec_strings()
-                            .iter()
-                            .flat_map(|&k| objects.get(k))
-                            .collect::<Vec<_>>(),
-                    )
+                .map(|vec| {
+                    vec.as_vec_strings()
+                        .iter()
+                        .flat_map(|&k| objects.get(k))
+                        .collect::<Vec<_>>()
                 })
                 .unwrap_or_default(),
             input_file_list_paths: value
@@ -108,9 +124,13 @@ impl<'a> AsPBXObject<'a> for PBXBuildPhase<'a> {
                 .get_vec("outputFileListPaths")
                 .map(|v| v.as_vec_strings()),
             run_only_for_deployment_postprocessing: value
-                .get_number("runOnlyForDeploymentPostprocessing")
-                .map(|v| v == &1)
+                .get_flag("runOnlyForDeploymentPostprocessing")
                 .unwrap_or_default(),
+            destination: value
+                .get_value("dstSubfolderSpec")
+                .map(TryInto::try_into)
+                .transpose()?,
+            destination_subpath: value.get_string("dstPath").filter(|p| !p.is_empty()),
             inner: if kind.is_run_script() {
                 Some(AsPBXObject::as_pbx_object(
                     Default::default(),
@@ -45,7 +45,7 @@ impl<'a> AsPBXObject<'a> for PBXBuildRule<'a> {
             compiler_spec: value.get_string("compilerSpec"),
             file_patterns: value.get_string("filePatterns"),
             file_type: value.get_string("fileType"),
-            is_editable: value.get_number("isEditable").map(|n| n == &1),
+            is_editable: value.get_flag("isEditable"),
             name: value.get_string("name"),
             output_files: value.get_vec("outputFiles").map(|v| v.as_vec_strings()),
             input_files: value.get_vec("inputFiles").map(|v| v.as_vec_strings()),
@@ -53,7 +53,7 @@ impl<'a> AsPBXObject<'a> for PBXBuildRule<'a> {
                 .get_vec("outputFilesCompilerFlags")
                 .map(|v| v.as_vec_strings()),
             script: value.get_string("script"),
-            run_once_per_architecture: value.get_number("runOncePerArchitecture").map(|n| n == &1),
+            run_once_per_architecture: value.get_flag("runOncePerArchitecture"),
         })
     }
 }
@@ -2,6 +2,9 @@ use crate::pbxproj::*;
 
 /// Part of [`PBXBuildPhase`] when [`PBXBuildPhaseKind`] is [`RunScript`]
 ///
+/// Keys shared with other build phases, i.e. `inputFileListPaths`, `outputFileListPaths` and
+/// `runOnlyForDeploymentPostprocessing`, are read by [`PBXBuildPhase`] itself.
+///
 /// [`RunScript`]: crate::pbxproj::PBXBuildPhaseKind::RunScript
 #[derive(Debug, derive_new::new)]
 pub struct PBXShellScriptBuildPhase<'a> {
@@ -32,21 +35,112 @@ impl<'a> AsPBXObject<'a> for PBXShellScriptBuildPhase<'a> {
     where
         Self: Sized + 'a,
     {
+        let strings = |key: &str| {
+            value
+                .get_vec(key)
+                .map(|v| v.as_vec_strings())
+                .unwrap_or_default()
+        };
+
         Ok(Self {
             name: value.get_string("name"),
-            input_paths: value.try_get_vec("inputPaths")?.as_vec_strings(),
-            output_paths: value.try_get_vec("outputPaths")?.as_vec_strings(),
+            input_paths: strings("inputPaths"),
+            output_paths: strings("outputPaths"),
             shell_path: value.get_string("shellPath"),
             shell_script: value.get_string("shellScript"),
-            show_env_vars_in_log: value
-                .get_number("runOnlyForDeploymentPostprocessing")
-                .map(|v| v == &1)
-                .unwrap_or_else(|| true),
-            always_out_of_date: value
-                .get_number("alwaysOutOfDate")
-                .map(|v| v == &1)
-                .unwrap_or_else(|| false),
+            show_env_vars_in_log: value.get_flag("showEnvVarsInLog").unwrap_or(true),
+            always_out_of_date: value.get_flag("alwaysOutOfDate").unwrap_or_default(),
             dependency_file: value.get_string("dependencyFile"),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbxproj::test_demo_file;
+
+    macro_rules! test_samples {
+        ($($name:ident => [$(($id:literal, $shell_path:literal)),*]),*) => {
+            $(#[test]
+                fn $name() {
+                    let project = test_demo_file!($name);
+                    let mut ids = project
+                        .get_ids_by_kind(|kind| {
+                            kind == &PBXObjectKind::PBXBuildPhase(PBXBuildPhaseKind::RunScript)
+                        })
+                        .into_iter()
+                        .map(String::as_str)
+                        .collect::<Vec<_>>();
+                    ids.sort_unstable();
+                    assert_eq!(ids, vec![$($id),*]);
+
+                    $(let phase = project.get::<PBXBuildPhase, _>($id).unwrap();
+                    assert_eq!(phase.input_file_list_paths, None);
+                    assert_eq!(phase.output_file_list_paths, None);
+                    assert!(!phase.run_only_when_installing());
+
+                    let script = phase.inner.unwrap();
+                    assert_eq!(script.name, None);
+                    assert_eq!(script.shell_path.unwrap(), $shell_path);
+                    assert!(script.shell_script.is_some());
+                    assert!(script.input_paths.is_empty());
+                    assert!(script.output_paths.is_empty());
+                    assert!(script.show_env_vars_in_log);
+                    assert!(!script.always_out_of_date);
+                    assert_eq!(script.dependency_file, None);)*
+                })*
+        };
+    }
+
+    test_samples![
+        demo2 => [("4F3228301C86F39200C073CA", "/bin/sh")],
+        demo3 => [("4F60FA5F1CEDBCEC006280A5", "/bin/sh")],
+        demo4 => [
+            ("4FD507291B00953B00768AD8", "/bin/sh"),
+            ("4FD999C01CA54F2C0079C9A1", "/bin/bash")
+        ]
+    ];
+
+    #[test]
+    fn missing_and_quoted_keys() {
+        let value = [
+            (
+                "isa",
+                PBXObjectKind::PBXBuildPhase(PBXBuildPhaseKind::RunScript).into(),
+            ),
+            ("shellScript", "swiftlint".into()),
+            ("showEnvVarsInLog", PBXValue::Number(0.into())),
+            ("alwaysOutOfDate", "1".into()),
+            ("runOnlyForDeploymentPostprocessing", PBXValue::Bool(true)),
+            (
+                "inputFileListPaths",
+                PBXValue::Vec(PBXVec::new(vec!["$(SRCROOT)/inputs.xcfilelist".into()])),
+            ),
+            ("dependencyFile", "$(DERIVED_FILE_DIR)/swiftlint.d".into()),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect::<PBXHashMap>();
+        let objects = PBXObjectCollection::default();
+        let phase = PBXShellScriptBuildPhase::as_pbx_object("id".into(), &value, &objects).unwrap();
+
+        assert!(phase.input_paths.is_empty());
+        assert!(phase.output_paths.is_empty());
+        assert!(!phase.show_env_vars_in_log);
+        assert!(phase.always_out_of_date);
+        assert_eq!(
+            phase.dependency_file.map(String::as_str),
+            Some("$(DERIVED_FILE_DIR)/swiftlint.d")
+        );
+
+        let phase = PBXBuildPhase::as_pbx_object("id".into(), &value, &objects).unwrap();
+        assert!(phase.inner.is_some());
+        assert!(phase.run_only_when_installing());
+        assert_eq!(
+            phase.input_file_list_paths.unwrap(),
+            vec!["$(SRCROOT)/inputs.xcfilelist"]
+        );
+        assert!(phase.output_file_list_paths.is_none());
+    }
+}
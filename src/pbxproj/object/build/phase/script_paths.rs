@@ -0,0 +1,192 @@
+use crate::pbxproj::*;
+use anyhow::{anyhow, Result};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Concrete input and output paths of a run script [`PBXBuildPhase`]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PBXScriptPaths {
+    /// Build phase id
+    pub id: String,
+    /// Input paths, including the ones listed in input file lists
+    pub inputs: BTreeSet<PathBuf>,
+    /// Output paths, including the ones listed in output file lists
+    pub outputs: BTreeSet<PathBuf>,
+}
+
+impl<'a> PBXBuildPhase<'a> {
+    /// Resolve input and output paths of a run script build phase.
+    ///
+    /// Paths and `.xcfilelist` entries are expanded with given build settings, and relative
+    /// paths are resolved against `SRCROOT`. Errors if a path refers to an undefined build
+    /// setting or a file list can't be read.
+    pub fn script_paths(&self, settings: &XCBuildSettings) -> Result<PBXScriptPaths> {
+        let script = self
+            .inner
+            .as_ref()
+            .ok_or_else(|| anyhow!("{:?} is not a run script build phase", self.id))?;
+
+        let collect = |paths: &Vec<&String>, file_lists: &Option<Vec<&String>>| {
+            let mut collected = BTreeSet::new();
+            for path in paths {
+                collected.extend(resolve_script_path(settings, path)?);
+            }
+            for file_list in file_lists.iter().flatten() {
+                let Some(file_list) = resolve_script_path(settings, file_list)? else {
+                    continue;
+                };
+                for path in read_file_list(&file_list)? {
+                    collected.extend(resolve_script_path(settings, &path)?);
+                }
+            }
+            Ok::<_, anyhow::Error>(collected)
+        };
+
+        Ok(PBXScriptPaths {
+            id: self.id.clone(),
+            inputs: collect(&script.input_paths, &self.input_file_list_paths)?,
+            outputs: collect(&script.output_paths, &self.output_file_list_paths)?,
+        })
+    }
+}
+
+impl<'a> PBXTarget<'a> {
+    /// Resolve input and output paths of every run script build phase for a given
+    /// configuration name.
+    ///
+    /// `source_root` is the directory containing the `.xcodeproj`, and `locations` are used
+    /// to resolve build directories such as `DERIVED_FILE_DIR`.
+    pub fn script_paths<P: AsRef<Path>>(
+        &self,
+        objects: &PBXObjectCollection,
+        configuration: &str,
+        source_root: P,
+        locations: &XCBuildLocations,
+    ) -> Result<Vec<PBXScriptPaths>> {
+        let settings =
+            self.build_settings_with_locations(objects, configuration, source_root, locations)?;
+        self.build_phases
+            .iter()
+            .filter(|phase| phase.is_run_script())
+            .map(|phase| phase.script_paths(&settings))
+            .collect()
+    }
+}
+
+/// Expand a given script path, resolving it against `SRCROOT` if relative.
+///
+/// None for blank paths, errors if path refers to an undefined build setting.
+pub(crate) fn resolve_script_path(
+    settings: &XCBuildSettings,
+    path: &str,
+) -> Result<Option<PathBuf>> {
+    let path = settings
+        .try_expand(path)
+        .map_err(|e| anyhow!("Failed to resolve script path: {e}"))?;
+    let path = path.trim();
+    let source_root = settings
+        .get("SRCROOT")
+        .map(String::as_str)
+        .unwrap_or_default();
+    Ok((!path.is_empty()).then(|| Path::new(source_root).join(path)))
+}
+
+/// Read `.xcfilelist` entries, skipping blank lines and `#` comments
+fn read_file_list(path: &Path) -> Result<Vec<String>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Failed to read file list {path:?}: {e}"))?;
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbxproj::test_demo_file;
+
+    #[test]
+    fn resolve_script_paths() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+        std::fs::write(
+            root.join("inputs.xcfilelist"),
+            "# Generated\n$(SRCROOT)/Sources/A.swift\n\nSources/B.swift\n",
+        )
+        .unwrap();
+
+        let mut project = test_demo_file!(demo2);
+        project.objects.update("4F3228301C86F39200C073CA", |phase| {
+            let strings = |paths: &[&str]| {
+                PBXValue::Vec(PBXVec::new(paths.iter().map(|&p| p.into()).collect()))
+            };
+            phase.insert_value(
+                "inputPaths",
+                strings(&[
+                    "$(SRCROOT)/Config.json",
+                    "$(TARGET_BUILD_DIR)/$(INFOPLIST_PATH)",
+                ]),
+            );
+            phase.insert_value(
+                "inputFileListPaths",
+                strings(&["$(SRCROOT)/inputs.xcfilelist"]),
+            );
+            phase.insert_value(
+                "outputPaths",
+                strings(&["$(DERIVED_FILE_DIR)/$(TARGET_NAME).out", "/tmp/out"]),
+            );
+        });
+
+        let target = project
+            .get_target_by_name("backbase-showcase-mobile-ios")
+            .unwrap();
+        let locations = XCBuildLocations::new("Showcase", "/derived");
+        let paths = target
+            .script_paths(&project, "Debug", root, &locations)
+            .unwrap();
+        assert_eq!(paths.len(), 1);
+        assert_eq!(
+            paths[0].inputs,
+            BTreeSet::from([
+                root.join("Config.json"),
+                root.join("Sources/A.swift"),
+                root.join("Sources/B.swift"),
+                PathBuf::from("/derived/Products/Debug-iphoneos/Backbase.app/Info.plist"),
+            ])
+        );
+        assert_eq!(
+            paths[0].outputs,
+            BTreeSet::from([
+                PathBuf::from(
+                    "/derived/Intermediates.noindex/Showcase.build/Debug-iphoneos/\
+                    backbase-showcase-mobile-ios.build/DerivedSources/\
+                    backbase-showcase-mobile-ios.out"
+                ),
+                PathBuf::from("/tmp/out"),
+            ])
+        );
+
+        std::fs::remove_file(root.join("inputs.xcfilelist")).unwrap();
+        assert!(target
+            .script_paths(&project, "Debug", root, &locations)
+            .is_err());
+
+        project.objects.update("4F3228301C86F39200C073CA", |phase| {
+            phase.remove_value("inputFileListPaths");
+            phase.insert_value(
+                "outputPaths",
+                PBXValue::Vec(PBXVec::new(vec!["$(UNDEFINED_DIR)/out".into()])),
+            );
+        });
+        let target = project
+            .get_target_by_name("backbase-showcase-mobile-ios")
+            .unwrap();
+        let error = target
+            .script_paths(&project, "Debug", root, &locations)
+            .unwrap_err();
+        assert!(error.to_string().contains("UNDEFINED_DIR"));
+    }
+}
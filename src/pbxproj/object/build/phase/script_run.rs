@@ -0,0 +1,254 @@
+use crate::pbxproj::*;
+use anyhow::{anyhow, bail, Result};
+use derive_is_enum_variant::is_enum_variant;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+/// Outcome of running a run script [`PBXBuildPhase`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, is_enum_variant)]
+pub enum PBXScriptRun {
+    /// Script ran and exited successfully
+    Ran,
+    /// Script was skipped because its outputs are newer than its inputs
+    Skipped,
+    /// Script was skipped because it only runs when installing, and
+    /// `DEPLOYMENT_POSTPROCESSING` isn't set to `YES`
+    SkippedNotInstalling,
+}
+
+impl<'a> PBXBuildPhase<'a> {
+    const DEFAULT_SHELL_PATH: &'static str = "/bin/sh";
+
+    /// Run a run script build phase locally, the way Xcode does.
+    ///
+    /// The script is written to a temporary file and run with `shellPath` from `SRCROOT`, with
+    /// given build settings exported as environment variables along with
+    /// `SCRIPT_INPUT_FILE_n`, `SCRIPT_OUTPUT_FILE_n` and their `_LIST_n` counterparts. Like in
+    /// Xcode, `SCRIPT_INPUT_FILE_LIST_n` and `SCRIPT_OUTPUT_FILE_LIST_n` are paths of the
+    /// `.xcfilelist` files themselves, not of the files they list.
+    ///
+    /// Phases that only run when installing are skipped unless `DEPLOYMENT_POSTPROCESSING` is
+    /// set to `YES`, as it is in install builds. Unless the phase is always out of date, the
+    /// script is also skipped when every output exists and is newer than every input.
+    ///
+    /// Errors if a path refers to an undefined build setting, or if the script exits
+    /// unsuccessfully.
+    pub fn run_script(&self, settings: &XCBuildSettings) -> Result<PBXScriptRun> {
+        let script = self
+            .inner
+            .as_ref()
+            .ok_or_else(|| anyhow!("{:?} is not a run script build phase", self.id))?;
+        let name = script.name.map(String::as_str).unwrap_or("Run Script");
+
+        if self.run_only_when_installing() && !settings.get_bool("DEPLOYMENT_POSTPROCESSING") {
+            return Ok(PBXScriptRun::SkippedNotInstalling);
+        }
+        if !script.always_out_of_date && self.script_paths(settings)?.is_up_to_date() {
+            return Ok(PBXScriptRun::Skipped);
+        }
+
+        let mut env = settings
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect::<Vec<_>>();
+        let mut export = |prefix: &str, paths: &[&String]| {
+            let mut resolved = vec![];
+            for path in paths {
+                resolved.extend(resolve_script_path(settings, path)?);
+            }
+            env.push((format!("{prefix}_COUNT"), resolved.len().to_string()));
+            for (index, path) in resolved.into_iter().enumerate() {
+                env.push((
+                    format!("{prefix}_{index}"),
+                    path.to_string_lossy().to_string(),
+                ));
+            }
+            Ok::<_, anyhow::Error>(())
+        };
+        export("SCRIPT_INPUT_FILE", &script.input_paths)?;
+        export("SCRIPT_OUTPUT_FILE", &script.output_paths)?;
+        export(
+            "SCRIPT_INPUT_FILE_LIST",
+            self.input_file_list_paths.as_deref().unwrap_or_default(),
+        )?;
+        export(
+            "SCRIPT_OUTPUT_FILE_LIST",
+            self.output_file_list_paths.as_deref().unwrap_or_default(),
+        )?;
+
+        #[cfg(feature = "with_tracing")]
+        if script.show_env_vars_in_log {
+            let mut env = env.iter().collect::<Vec<_>>();
+            env.sort();
+            for (key, value) in env {
+                tracing::info!("export {key}={value:?}");
+            }
+        }
+
+        let mut script_file = tempfile::Builder::new()
+            .prefix("xcodeproj-script-")
+            .suffix(".sh")
+            .tempfile()?;
+        script_file.write_all(
+            script
+                .shell_script
+                .map(String::as_str)
+                .unwrap_or_default()
+                .as_bytes(),
+        )?;
+        script_file.flush()?;
+
+        let mut command = Command::new(
+            script
+                .shell_path
+                .map(String::as_str)
+                .unwrap_or(Self::DEFAULT_SHELL_PATH),
+        );
+        command.arg(script_file.path()).envs(env);
+        if let Some(source_root) = settings.get("SRCROOT").filter(|root| !root.is_empty()) {
+            command.current_dir(source_root);
+        }
+        let status = command
+            .status()
+            .map_err(|e| anyhow!("Failed to run {name:?}: {e}"))?;
+        if !status.success() {
+            bail!("{name:?} failed with {status}")
+        }
+
+        Ok(PBXScriptRun::Ran)
+    }
+}
+
+impl<'a> PBXTarget<'a> {
+    /// Run every run script build phase for a given configuration name in order, stopping at
+    /// the first failure.
+    ///
+    /// `source_root` is the directory containing the `.xcodeproj`, and `locations` are used
+    /// to resolve build directories such as `BUILT_PRODUCTS_DIR`. Settings are those of a
+    /// regular build, so phases that only run when installing are skipped. To run them, set
+    /// `DEPLOYMENT_POSTPROCESSING` and use [`PBXBuildPhase::run_script`].
+    pub fn run_scripts<P: AsRef<Path>>(
+        &self,
+        objects: &PBXObjectCollection,
+        configuration: &str,
+        source_root: P,
+        locations: &XCBuildLocations,
+    ) -> Result<Vec<PBXScriptRun>> {
+        let settings =
+            self.build_settings_with_locations(objects, configuration, source_root, locations)?;
+        self.build_phases
+            .iter()
+            .filter(|phase| phase.is_run_script())
+            .map(|phase| phase.run_script(&settings))
+            .collect()
+    }
+}
+
+impl PBXScriptPaths {
+    /// Whether every output exists and is newer than every input.
+    ///
+    /// False without outputs, as there is nothing to compare inputs with.
+    pub fn is_up_to_date(&self) -> bool {
+        fn modified(path: &PathBuf) -> Option<SystemTime> {
+            std::fs::metadata(path).and_then(|m| m.modified()).ok()
+        }
+
+        if self.outputs.is_empty() {
+            return false;
+        }
+        let Some(oldest_output) = self
+            .outputs
+            .iter()
+            .map(modified)
+            .collect::<Option<Vec<_>>>()
+            .and_then(|times| times.into_iter().min())
+        else {
+            return false;
+        };
+
+        self.inputs
+            .iter()
+            .map(modified)
+            .all(|input| input.is_some_and(|input| input <= oldest_output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbxproj::test_demo_file;
+
+    #[test]
+    #[cfg(unix)]
+    fn run_script() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+        std::fs::write(root.join("input.txt"), "input").unwrap();
+
+        let mut project = test_demo_file!(demo2);
+        project.objects.update("4F3228301C86F39200C073CA", |phase| {
+            let strings = |paths: &[&str]| {
+                PBXValue::Vec(PBXVec::new(paths.iter().map(|&p| p.into()).collect()))
+            };
+            phase.insert_value("inputPaths", strings(&["input.txt"]));
+            phase.insert_value(
+                "outputPaths",
+                strings(&["$(BUILT_PRODUCTS_DIR)/$(TARGET_NAME).txt"]),
+            );
+            phase.insert_value(
+                "shellScript",
+                "mkdir -p \"$BUILT_PRODUCTS_DIR\"\n\
+                echo \"$CONFIGURATION $SCRIPT_INPUT_FILE_COUNT\" > \"$SCRIPT_OUTPUT_FILE_0\"\n",
+            );
+        });
+
+        let locations = XCBuildLocations::new("Showcase", root.join("Build"));
+        let target = project
+            .get_target_by_name("backbase-showcase-mobile-ios")
+            .unwrap();
+        let runs = target
+            .run_scripts(&project, "Debug", root, &locations)
+            .unwrap();
+        assert_eq!(runs, vec![PBXScriptRun::Ran]);
+        let output = root.join("Build/Products/Debug-iphoneos/backbase-showcase-mobile-ios.txt");
+        assert_eq!(std::fs::read_to_string(&output).unwrap(), "Debug 1\n");
+
+        // Output is newer than input now
+        let runs = target
+            .run_scripts(&project, "Debug", root, &locations)
+            .unwrap();
+        assert_eq!(runs, vec![PBXScriptRun::Skipped]);
+
+        std::fs::remove_file(&output).unwrap();
+        project.objects.update("4F3228301C86F39200C073CA", |phase| {
+            phase.insert_value("shellScript", "exit 3");
+        });
+        let target = project
+            .get_target_by_name("backbase-showcase-mobile-ios")
+            .unwrap();
+        assert!(target
+            .run_scripts(&project, "Debug", root, &locations)
+            .is_err());
+
+        project.objects.update("4F3228301C86F39200C073CA", |phase| {
+            phase.insert_value("runOnlyForDeploymentPostprocessing", 1);
+        });
+        let target = project
+            .get_target_by_name("backbase-showcase-mobile-ios")
+            .unwrap();
+        let runs = target
+            .run_scripts(&project, "Debug", root, &locations)
+            .unwrap();
+        assert_eq!(runs, vec![PBXScriptRun::SkippedNotInstalling]);
+
+        let mut settings = target
+            .build_settings_with_locations(&project, "Debug", root, &locations)
+            .unwrap();
+        settings.insert("DEPLOYMENT_POSTPROCESSING", "YES");
+        let phase = project.get_build_phase("4F3228301C86F39200C073CA").unwrap();
+        let error = phase.run_script(&settings).unwrap_err();
+        assert!(error.to_string().contains("exit status: 3"));
+    }
+}
@@ -0,0 +1,375 @@
+use crate::pbxproj::*;
+use anyhow::{anyhow, bail, Result};
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::path::Path;
+use tap::Pipe;
+
+/// Resolved build settings of a [`PBXTarget`] for a given configuration.
+///
+/// Settings are layered the same way Xcode does: builtin values, then project `.xcconfig` and
+/// configuration, then target `.xcconfig` and configuration. `$(inherited)` is replaced with the
+/// value of the layer below, and every other `$(VAR)` or `${VAR}` reference is expanded.
+#[derive(Debug, Default, Clone, PartialEq, Eq, derive_new::new, derive_deref_rs::Deref)]
+pub struct XCBuildSettings(HashMap<String, String>);
+
+impl XCBuildSettings {
+    const MAX_EXPANSION_DEPTH: usize = 16;
+
+    /// Insert a raw build setting value
+    pub fn insert<K: Into<String>, V: Into<String>>(&mut self, key: K, value: V) {
+        self.0.insert(key.into(), value.into());
+    }
+
+    /// Get build setting as a list of values, splitting it like a shell would.
+    pub fn get_list(&self, key: &str) -> Vec<String> {
+        self.0
+            .get(key)
+            .map(|value| split_setting(value))
+            .unwrap_or_default()
+    }
+
+    /// Whether build setting is set to `YES`
+    pub fn get_bool(&self, key: &str) -> bool {
+        self.0.get(key).map(|v| v == "YES").unwrap_or_default()
+    }
+
+    /// Layer raw `buildSettings` on top of current values.
+    ///
+    /// NOTE: Conditional settings, i.e. `KEY[sdk=iphoneos*]`, are ignored.
+    pub fn layer(&mut self, settings: &PBXHashMap) {
+        for (key, value) in settings.iter() {
+            if let Some(value) = setting_to_string(value) {
+                self.layer_value(key, &value);
+            }
+        }
+    }
+
+    /// Layer settings of an `.xcconfig` file on top of current values, following `#include`
+    /// directives relative to the including file.
+    ///
+    /// Errors if the file or an included file can't be read, unless included with `#include?`.
+    ///
+    /// NOTE: Like in [`XCBuildSettings::layer`], conditional settings are ignored.
+    pub fn layer_xcconfig<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        self.layer_xcconfig_with_depth(path.as_ref(), 0)
+    }
+
+    /// Layer base `.xcconfig` and build settings of a given configuration.
+    ///
+    /// A base `.xcconfig` missing from disk is skipped with a warning, the same way Xcode does.
+    pub(crate) fn layer_configuration(
+        &mut self,
+        configuration: &XCBuildConfiguration,
+        objects: &PBXObjectCollection,
+        source_root: &Path,
+    ) -> Result<()> {
+        if let Some(reference) = configuration.base_configuration.as_ref() {
+            let path = reference.full_path(objects, source_root)?;
+            if path.exists() {
+                self.layer_xcconfig(&path)?;
+            } else {
+                #[cfg(feature = "with_tracing")]
+                tracing::warn!("Skipping missing base configuration {path:?}");
+            }
+        }
+
+        self.layer(configuration.build_settings);
+        Ok(())
+    }
+
+    fn layer_xcconfig_with_depth(&mut self, path: &Path, depth: usize) -> Result<()> {
+        if depth > Self::MAX_EXPANSION_DEPTH {
+            anyhow::bail!("Too many nested includes at {path:?}")
+        }
+
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read xcconfig {path:?}: {e}"))?;
+        let directory = path.parent().unwrap_or(Path::new(""));
+
+        for line in content.lines() {
+            let line = line.split("//").next().unwrap_or_default().trim();
+            if let Some(include) = line.strip_prefix("#include") {
+                let (optional, include) = match include.strip_prefix('?') {
+                    Some(include) => (true, include),
+                    None => (false, include),
+                };
+                let include = directory.join(include.trim().trim_matches('"'));
+                if optional && !include.exists() {
+                    continue;
+                }
+                self.layer_xcconfig_with_depth(&include, depth + 1)?;
+            } else if let Some((key, value)) = line.split_once('=') {
+                self.layer_value(key.trim(), value.trim().trim_end_matches(';'));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn layer_value(&mut self, key: &str, value: &str) {
+        if key.is_empty() || key.contains('[') {
+            return;
+        }
+
+        let inherited = self.0.get(key).map(String::as_str).unwrap_or_default();
+        let value = value
+            .replace("$(inherited)", inherited)
+            .replace("${inherited}", inherited)
+            .trim()
+            .to_string();
+
+        self.0.insert(key.to_string(), value);
+    }
+
+    /// Expand `$(VAR)` and `${VAR}` references in a given value.
+    ///
+    /// Undefined references are expanded to an empty string, and the following modifiers are
+    /// supported: `lower`, `upper`, `identifier`, `c99extidentifier` and `rfc1034identifier`.
+    pub fn expand(&self, value: &str) -> String {
+        self.expand_with_depth(value, 0)
+    }
+
+    /// Expand references like [`XCBuildSettings::expand`], but error on references to
+    /// undefined settings instead of expanding them to an empty string.
+    pub fn try_expand(&self, value: &str) -> Result<String> {
+        let undefined = references(value)
+            .into_iter()
+            .filter(|name| !self.0.contains_key(*name))
+            .collect::<Vec<_>>();
+        if !undefined.is_empty() {
+            bail!("Undefined build settings {undefined:?} in {value:?}")
+        }
+        Ok(self.expand(value))
+    }
+
+    /// Returns new build settings with all values expanded
+    pub fn expanded(&self) -> Self {
+        self.0
+            .iter()
+            .map(|(k, v)| (k.clone(), self.expand(v)))
+            .collect::<HashMap<_, _>>()
+            .pipe(Self)
+    }
+
+    fn expand_with_depth(&self, value: &str, depth: usize) -> String {
+        let mut output = String::with_capacity(value.len());
+        let mut rest = value;
+
+        while let Some(start) = rest.find('$') {
+            output.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let close = match after.chars().next() {
+                Some('(') => ')',
+                Some('{') => '}',
+                _ => {
+                    output.push('$');
+                    rest = after;
+                    continue;
+                }
+            };
+
+            match after.find(close) {
+                Some(end) => {
+                    output.push_str(&self.resolve_reference(&after[1..end], depth));
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    output.push_str(&rest[start..]);
+                    rest = "";
+                }
+            }
+        }
+
+        output.push_str(rest);
+        output
+    }
+
+    fn resolve_reference(&self, reference: &str, depth: usize) -> String {
+        let mut parts = reference.split(':');
+        let name = parts.next().unwrap_or_default();
+        let mut value = match self.0.get(name) {
+            Some(value) if depth < Self::MAX_EXPANSION_DEPTH => {
+                self.expand_with_depth(value, depth + 1)
+            }
+            Some(value) => value.clone(),
+            None => String::default(),
+        };
+
+        for modifier in parts {
+            value = match modifier {
+                "lower" => value.to_lowercase(),
+                "upper" => value.to_uppercase(),
+                "identifier" | "c99extidentifier" => c99_identifier(&value),
+                "rfc1034identifier" => value
+                    .chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+                    .collect(),
+                _ => value,
+            }
+        }
+
+        value
+    }
+}
+
+/// Value as a C identifier, with non-alphanumeric characters replaced by `_`, as the
+/// `c99extidentifier` modifier expands it
+pub(crate) fn c99_identifier(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Names of settings referenced by a given value, without modifiers
+fn references(value: &str) -> Vec<&str> {
+    let mut names = vec![];
+    let mut rest = value;
+    while let Some(start) = rest.find('$') {
+        let after = &rest[start + 1..];
+        let close = match after.chars().next() {
+            Some('(') => ')',
+            Some('{') => '}',
+            _ => {
+                rest = after;
+                continue;
+            }
+        };
+        let Some(end) = after.find(close) else {
+            break;
+        };
+        names.extend(after[1..end].split(':').next());
+        rest = &after[end + 1..];
+    }
+    names
+}
+
+fn setting_to_string(value: &PBXValue) -> Option<String> {
+    match value {
+        PBXValue::String(value) => Some(value.clone()),
+        PBXValue::Number(value) => Some(value.to_string()),
+        PBXValue::Bool(value) => Some(if *value { "YES" } else { "NO" }.to_string()),
+        PBXValue::Vec(values) => values
+            .iter()
+            .flat_map(setting_to_string)
+            .map(|v| {
+                if v.contains(char::is_whitespace) {
+                    format!("\"{v}\"")
+                } else {
+                    v
+                }
+            })
+            .join(" ")
+            .pipe(Some),
+        _ => None,
+    }
+}
+
+/// Split build setting value into a list, respecting quotes.
+pub(crate) fn split_setting(value: &str) -> Vec<String> {
+    shlex::split(value).unwrap_or_else(|| value.split_whitespace().map(String::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbxproj::test_demo_file;
+
+    #[test]
+    fn expand_references() {
+        let mut settings = XCBuildSettings::default();
+        settings.insert("TARGET_NAME", "My App");
+        settings.insert("PRODUCT_NAME", "$(TARGET_NAME)");
+        settings.insert("SELF", "$(SELF)");
+
+        assert_eq!(settings.expand("${PRODUCT_NAME}.app"), "My App.app");
+        assert_eq!(
+            settings.expand("com.$(PRODUCT_NAME:rfc1034identifier:lower)"),
+            "com.my-app"
+        );
+        assert_eq!(
+            settings.expand("$(PRODUCT_NAME:c99extidentifier)"),
+            "My_App"
+        );
+        assert_eq!(settings.expand("$(UNDEFINED)/path"), "/path");
+        assert_eq!(
+            settings.try_expand("$(PRODUCT_NAME:lower)").unwrap(),
+            "my app"
+        );
+        assert!(settings.try_expand("$(UNDEFINED)/path").is_err());
+        assert_eq!(settings.expand("$$ and $(broken"), "$$ and $(broken");
+        // Cyclic references are left unexpanded
+        assert_eq!(settings.expand("$(SELF)"), "$(SELF)");
+    }
+
+    #[test]
+    fn layer_inherited() {
+        let project = test_demo_file!(demo8);
+        let target = project.get_target_by_name("test").unwrap();
+        let settings = target.build_settings(&project, "Debug", "/root").unwrap();
+
+        assert_eq!(
+            settings.get_list("GCC_PREPROCESSOR_DEFINITIONS"),
+            vec!["DEBUG=1"]
+        );
+        assert_eq!(
+            settings.get_list("LD_RUNPATH_SEARCH_PATHS"),
+            vec!["@executable_path/Frameworks"]
+        );
+        assert_eq!(settings.get("PRODUCT_NAME").unwrap(), "test");
+        assert_eq!(settings.get("SRCROOT").unwrap(), "/root");
+        assert_eq!(settings.get("IPHONEOS_DEPLOYMENT_TARGET").unwrap(), "9.3");
+        assert!(settings.get_bool("CLANG_ENABLE_OBJC_ARC"));
+        assert!(settings.get("CODE_SIGN_IDENTITY[sdk=iphoneos*]").is_none());
+    }
+
+    #[test]
+    fn layer_base_xcconfig() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+        std::fs::create_dir_all(root.join("Configuration")).unwrap();
+        std::fs::write(
+            root.join("Configuration/SampleCode.xcconfig"),
+            "// Sample code settings\n\
+            #include? \"Missing.xcconfig\"\n\
+            #include \"Shared.xcconfig\"\n\
+            OTHER_SWIFT_FLAGS = $(inherited) -DSAMPLE // Appended\n\
+            SWIFT_VERSION = 4.2\n\
+            SAMPLE_CODE_DISAMBIGUATOR = ${DEVELOPMENT_TEAM};\n\
+            CODE_SIGN_IDENTITY[sdk=iphoneos*] = iPhone Developer\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("Configuration/Shared.xcconfig"),
+            "OTHER_SWIFT_FLAGS = -DSHARED\n",
+        )
+        .unwrap();
+
+        let project = test_demo_file!(demo10);
+        let target = project.get_target_by_name("Scrumdinger").unwrap();
+        let settings = target.build_settings(&project, "Debug", root).unwrap();
+
+        assert_eq!(
+            settings.get_list("OTHER_SWIFT_FLAGS"),
+            vec!["-DSHARED", "-DSAMPLE"]
+        );
+        assert_eq!(
+            settings.get("SAMPLE_CODE_DISAMBIGUATOR").unwrap(),
+            "XXXXXXXXXX"
+        );
+        // Configuration settings win over base configuration ones
+        assert_eq!(settings.get("SWIFT_VERSION").unwrap(), "5.0");
+        assert!(settings.get("CODE_SIGN_IDENTITY[sdk=iphoneos*]").is_none());
+
+        std::fs::remove_file(root.join("Configuration/Shared.xcconfig")).unwrap();
+        assert!(target.build_settings(&project, "Debug", root).is_err());
+    }
+
+    #[test]
+    fn missing_configuration() {
+        let project = test_demo_file!(demo8);
+        let target = project.get_target_by_name("test").unwrap();
+        assert!(target.build_settings(&project, "Staging", "/root").is_err());
+    }
+}
@@ -0,0 +1,69 @@
+use crate::pbxproj::*;
+use std::cell::OnceCell;
+
+/// Lazily resolved views of a [`PBXObjectCollection`].
+///
+/// Each view list is resolved at most once, on first access, so repeated queries don't
+/// rebuild borrowed views.
+#[derive(Debug)]
+pub struct PBXObjectCache<'a> {
+    objects: &'a PBXObjectCollection,
+    targets: OnceCell<Vec<PBXTarget<'a>>>,
+    projects: OnceCell<Vec<PBXProject<'a>>>,
+    build_files: OnceCell<Vec<PBXBuildFile<'a>>>,
+    files: OnceCell<Vec<PBXFSReference<'a>>>,
+    groups: OnceCell<Vec<PBXFSReference<'a>>>,
+}
+
+impl<'a> PBXObjectCache<'a> {
+    /// Create new cache of objects
+    pub fn new(objects: &'a PBXObjectCollection) -> Self {
+        Self {
+            objects,
+            targets: OnceCell::new(),
+            projects: OnceCell::new(),
+            build_files: OnceCell::new(),
+            files: OnceCell::new(),
+            groups: OnceCell::new(),
+        }
+    }
+
+    /// Get all PBXTarget
+    pub fn targets(&self) -> &[PBXTarget<'a>] {
+        self.targets.get_or_init(|| self.objects.targets())
+    }
+
+    /// Get all PBXProject
+    pub fn projects(&self) -> &[PBXProject<'a>] {
+        self.projects.get_or_init(|| self.objects.projects())
+    }
+
+    /// Get all build files
+    pub fn build_files(&self) -> &[PBXBuildFile<'a>] {
+        self.build_files.get_or_init(|| self.objects.build_files())
+    }
+
+    /// Get all source code files
+    pub fn files(&self) -> &[PBXFSReference<'a>] {
+        self.files.get_or_init(|| self.objects.files())
+    }
+
+    /// Get all groups
+    pub fn groups(&self) -> &[PBXFSReference<'a>] {
+        self.groups.get_or_init(|| self.objects.groups())
+    }
+
+    /// Get PBXTarget by the target name
+    pub fn get_target_by_name(&self, name: &str) -> Option<&PBXTarget<'a>> {
+        self.targets()
+            .iter()
+            .find(|target| target.name.map(|n| n == name).unwrap_or_default())
+    }
+}
+
+impl PBXObjectCollection {
+    /// Get lazily resolved views of objects
+    pub fn cache(&self) -> PBXObjectCache<'_> {
+        PBXObjectCache::new(self)
+    }
+}
@@ -3,11 +3,30 @@
 // use rand::{thread_rng, Rng};
 use crate::pbxproj::*;
 use anyhow::Result;
-use std::collections::HashMap;
+use indexmap::IndexMap;
+use std::collections::HashSet;
 
 /// [`PBXObject`] storage with convenient helper methods
-#[derive(Default, Debug, derive_new::new, derive_deref_rs::Deref)]
-pub struct PBXObjectCollection(pub(crate) HashMap<String, PBXHashMap>);
+///
+/// Objects are indexed by isa, name and parent group, so that accessors only resolve the
+/// objects they return. Mutations should go through [`PBXObjectCollection::insert`],
+/// [`PBXObjectCollection::remove`] or [`PBXObjectCollection::update`] to keep indexes in sync,
+/// so objects are only exposed mutably through them.
+///
+/// Objects keep source order, new objects are appended.
+#[derive(Default, Debug)]
+pub struct PBXObjectCollection {
+    objects: IndexMap<String, PBXHashMap>,
+    index: PBXObjectIndex,
+}
+
+impl std::ops::Deref for PBXObjectCollection {
+    type Target = IndexMap<String, PBXHashMap>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.objects
+    }
+}
 
 /// Get PBXObject from PBXHashMap and PBXObjectCollection
 pub trait AsPBXObject<'a> {
@@ -22,13 +41,93 @@ pub trait AsPBXObject<'a> {
 }
 
 impl PBXObjectCollection {
+    /// Create new collection from objects by id, keeping their order
+    pub fn new(objects: IndexMap<String, PBXHashMap>) -> Self {
+        let mut index = PBXObjectIndex::default();
+        for (id, object) in objects.iter() {
+            index.insert(id, object);
+        }
+        Self { objects, index }
+    }
+
+    /// Get raw object with a given id
+    pub fn get_object(&self, key: &str) -> Option<&PBXHashMap> {
+        self.objects.get(key)
+    }
+
+    /// Insert object with a given id, returning the object it replaced if any.
+    ///
+    /// Replaced objects keep their position, new objects are appended.
+    pub fn insert(&mut self, id: String, object: PBXHashMap) -> Option<PBXHashMap> {
+        if let Some(previous) = self.objects.get(&id) {
+            self.index.remove(&id, previous);
+        }
+        self.index.insert(&id, &object);
+        self.objects.insert(id, object)
+    }
+
+    /// Insert object with a new generated id, returning the id
+    pub fn push<T: Into<PBXHashMap>>(&mut self, object: T) -> String {
+        let id = self.generate_id();
+        self.insert(id.clone(), object.into());
+        id
+    }
+
+    /// Generate new 24 characters hex id, unique within the collection
+    pub fn generate_id(&self) -> String {
+        use std::hash::{BuildHasher, Hash, Hasher};
+
+        let state = std::collections::hash_map::RandomState::new();
+        (0..)
+            .map(|seed: u64| {
+                let mut hasher = state.build_hasher();
+                (seed, self.objects.len()).hash(&mut hasher);
+                let high = hasher.finish();
+                high.hash(&mut hasher);
+                format!("{high:016X}{:08X}", hasher.finish() as u32)
+            })
+            .find(|id| !self.objects.contains_key(id))
+            .unwrap()
+    }
+
+    /// Remove object with a given id, keeping source order of remaining objects.
+    ///
+    /// Takes linear time, use [`PBXObjectCollection::remove_all`] to remove many objects.
+    pub fn remove(&mut self, id: &str) -> Option<PBXHashMap> {
+        let object = self.objects.shift_remove(id)?;
+        self.index.remove(id, &object);
+        Some(object)
+    }
+
+    /// Remove objects with given ids in a single pass, keeping source order of remaining
+    /// objects.
+    pub fn remove_all(&mut self, ids: &HashSet<String>) {
+        let index = &mut self.index;
+        self.objects.retain(|id, object| {
+            let is_removed = ids.contains(id);
+            if is_removed {
+                index.remove(id, object);
+            }
+            !is_removed
+        });
+    }
+
+    /// Update object with a given id in place, returning None if object doesn't exist.
+    pub fn update<R>(&mut self, id: &str, f: impl FnOnce(&mut PBXHashMap) -> R) -> Option<R> {
+        let object = self.objects.get_mut(id)?;
+        self.index.remove(id, object);
+        let result = f(object);
+        self.index.insert(id, object);
+        Some(result)
+    }
+
     /// Get T from collection
     pub fn get<'a, T, S>(&'a self, key: S) -> Option<T>
     where
         T: AsPBXObject<'a> + 'a,
         S: AsRef<str>,
     {
-        self.0.get(key.as_ref()).and_then(|value| {
+        self.objects.get(key.as_ref()).and_then(|value| {
             AsPBXObject::as_pbx_object(key.as_ref().to_string(), value, self).ok()
         })
     }
@@ -66,84 +165,67 @@ impl PBXObjectCollection {
             .collect::<Vec<_>>()
     }
 
+    /// Get vector of T of objects whose isa matches predict, using isa index.
+    pub fn get_vec_by_kind<'a, T: AsPBXObject<'a> + 'a>(
+        &'a self,
+        predict: impl Fn(&PBXObjectKind) -> bool,
+    ) -> Vec<T> {
+        self.get_vec(self.get_ids_by_kind(predict))
+    }
+
+    /// Get ids of objects whose isa matches predict, using isa index.
+    pub fn get_ids_by_kind(&self, predict: impl Fn(&PBXObjectKind) -> bool) -> Vec<&String> {
+        self.index
+            .kinds()
+            .filter(|(kind, _)| predict(kind))
+            .flat_map(|(_, ids)| ids.iter())
+            .collect()
+    }
+
     /// Get all PBXTarget
     pub fn targets<'a>(&'a self) -> Vec<PBXTarget<'a>> {
-        self.get_vec_by(|(_, v)| {
-            v.get_kind("isa")
-                .map(|k| k.is_pbx_target())
-                .unwrap_or_default()
-        })
+        self.get_vec_by_kind(|k| k.is_pbx_target())
     }
 
     /// Get all PBXProject
     pub fn projects<'a>(&'a self) -> Vec<PBXProject<'a>> {
-        self.get_vec_by(|(_, v)| {
-            v.get_kind("isa")
-                .map(|k| k.is_pbx_project())
-                .unwrap_or_default()
-        })
+        self.get_vec_by_kind(|k| k.is_pbx_project())
     }
 
     /// Get all build phases
     pub fn build_phases<'a>(&'a self) -> Vec<PBXBuildPhase<'a>> {
-        self.get_vec_by(|(_, v)| {
-            v.get_kind("isa")
-                .map(|k| k.is_pbx_build_phase())
-                .unwrap_or_default()
-        })
+        self.get_vec_by_kind(|k| k.is_pbx_build_phase())
     }
 
     /// Get all build phases
     pub fn build_configurations<'a>(&'a self) -> Vec<XCBuildConfiguration<'a>> {
-        self.get_vec_by(|(_, v)| {
-            v.get_kind("isa")
-                .map(|k| k.is_xc_build_configuration())
-                .unwrap_or_default()
-        })
+        self.get_vec_by_kind(|k| k.is_xc_build_configuration())
     }
 
     /// Get all build phases
     pub fn build_files<'a>(&'a self) -> Vec<PBXBuildFile<'a>> {
-        self.get_vec_by(|(_, v)| {
-            v.get_kind("isa")
-                .map(|k| k.is_pbx_build_file())
-                .unwrap_or_default()
-        })
+        self.get_vec_by_kind(|k| k.is_pbx_build_file())
     }
 
     /// Get all build phases
     pub fn build_rules<'a>(&'a self) -> Vec<PBXBuildRule<'a>> {
-        self.get_vec_by(|(_, v)| {
-            v.get_kind("isa")
-                .map(|k| k.is_pbx_build_rule())
-                .unwrap_or_default()
-        })
+        self.get_vec_by_kind(|k| k.is_pbx_build_rule())
     }
 
     /// Get all source code files
     pub fn files<'a>(&'a self) -> Vec<PBXFSReference<'a>> {
-        self.get_vec_by(|(_, v)| {
-            v.get_kind("isa")
-                .map(|k| {
-                    k.is_pbx_fsreference()
-                        && k.as_pbxfs_reference()
-                            .map(|r| r.is_file())
-                            .unwrap_or_default()
-                })
+        self.get_vec_by_kind(|k| {
+            k.as_pbxfs_reference()
+                .map(|r| r.is_file())
                 .unwrap_or_default()
         })
     }
 
     /// Get all groups
     pub fn groups<'a>(&'a self) -> Vec<PBXFSReference<'a>> {
-        self.get_vec_by(|(_, v)| {
-            v.get_kind("isa")
-                .map(|k| {
-                    k.is_pbx_fsreference()
-                        && k.as_pbxfs_reference()
-                            .map(|r| r.is_group())
-                            .unwrap_or_default()
-                })
+        self.get_vec_by_kind(|k| {
+            k.as_pbxfs_reference()
+                .map(|r| r.is_group())
                 .unwrap_or_default()
         })
     }
@@ -152,20 +234,12 @@ impl PBXObjectCollection {
     pub fn swift_package_product_dependencies<'a>(
         &'a self,
     ) -> Vec<XCSwiftPackageProductDependency<'a>> {
-        self.get_vec_by(|(_, v)| {
-            v.get_kind("isa")
-                .map(|k| k.is_xc_swift_package_product_dependency())
-                .unwrap_or_default()
-        })
+        self.get_vec_by_kind(|k| k.is_xc_swift_package_product_dependency())
     }
 
     /// Get All XCRemoteSwiftPackageReference Objects
     pub fn swift_package_references<'a>(&'a self) -> Vec<XCRemoteSwiftPackageReference<'a>> {
-        self.get_vec_by(|(_, v)| {
-            v.get_kind("isa")
-                .map(|k| k.is_xc_remote_swift_package_reference())
-                .unwrap_or_default()
-        })
+        self.get_vec_by_kind(|k| k.is_xc_remote_swift_package_reference())
     }
 
     /// Get PBXTarget
@@ -223,23 +297,31 @@ impl PBXObjectCollection {
         &'a self,
         name_or_path: S,
     ) -> Option<PBXFSReference<'a>> {
-        let name = name_or_path.as_ref();
-        self.groups().into_iter().find(|o| {
-            if let Some(n) = o.name {
-                return n == name;
-            } else if let Some(p) = o.path {
-                return p == name;
-            } else {
-                false
-            }
-        })
+        self.index
+            .by_name(name_or_path.as_ref())
+            .find_map(|id| self.get_group(id))
+    }
+
+    /// Get the group containing fs object with the given id
+    pub fn get_fs_reference_parent<'a>(&'a self, key: &str) -> Option<PBXFSReference<'a>> {
+        self.index.parent(key).and_then(|id| self.get_group(id))
+    }
+
+    /// Whether the given id is a main group of a PBXProject
+    pub fn is_main_group(&self, key: &str) -> bool {
+        // Main groups are never children of another group
+        self.index.parent(key).is_none()
+            && self
+                .get_ids_by_kind(|k| k.is_pbx_project())
+                .into_iter()
+                .any(|id| self.objects[id].get_str("mainGroup") == Some(key))
     }
 
     /// Get build configurations shearing a given baseConfiguration id
     pub fn get_build_configurations_by_base_id<S: AsRef<str>>(
         &self,
         id: S,
-    ) -> Vec<XCBuildConfiguration> {
+    ) -> Vec<XCBuildConfiguration<'_>> {
         let key = id.as_ref();
         self.get_vec_by(move |(_, v)| {
             v.get_kind("isa")
@@ -269,12 +351,161 @@ impl PBXObjectCollection {
 
     /// Get PBXTarget by the target name
     pub fn get_target_by_name<'a>(&'a self, name: &'a str) -> Option<PBXTarget<'a>> {
-        self.targets().into_iter().find(|target| {
-            if let Some(target_name) = target.name {
-                target_name == name
-            } else {
-                false
-            }
+        self.index.by_name(name).find_map(|id| {
+            let is_target = self.objects[id]
+                .get_kind("isa")
+                .map(|k| k.is_pbx_target())
+                .unwrap_or_default();
+            is_target.then(|| self.get_target(id)).flatten()
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbxproj::test_demo_file;
+
+    #[test]
+    fn indexed_accessors_match_full_scan() {
+        let project = test_demo_file!(demo4);
+        let mut indexed = project
+            .targets()
+            .into_iter()
+            .map(|t| t.id)
+            .collect::<Vec<_>>();
+        let mut scanned = project
+            .get_vec_by::<PBXTarget>(|(_, v)| {
+                v.get_kind("isa")
+                    .map(|k| k.is_pbx_target())
+                    .unwrap_or_default()
+            })
+            .into_iter()
+            .map(|t| t.id)
+            .collect::<Vec<_>>();
+        indexed.sort();
+        scanned.sort();
+
+        assert_eq!(indexed, scanned);
+        assert_eq!(project.files().len(), project.cache().files().len());
+        assert_eq!(
+            project.groups().len(),
+            project
+                .get_vec_by::<PBXFSReference>(|(_, v)| {
+                    v.get_kind("isa")
+                        .and_then(|k| k.as_pbxfs_reference())
+                        .map(|k| k.is_group())
+                        .unwrap_or_default()
+                })
+                .len()
+        );
+        assert!(project.get_target_by_name("BackbaseCXPTests").is_some());
+        assert!(project.get_group_by_name_or_path("Security").is_some());
+    }
+
+    #[test]
+    fn index_maintained_on_mutation() {
+        let mut project = test_demo_file!(demo1);
+        let id = project.get_target_by_name("Wordle").unwrap().id;
+        let objects = project.objects_mut();
+
+        objects.update(&id, |target| {
+            target
+                .0
+                .insert("name".into(), PBXValue::String("Renamed".into()))
+        });
+        assert!(objects.get_target_by_name("Wordle").is_none());
+        assert!(objects.get_target_by_name("Renamed").is_some());
+
+        let target = objects.remove(&id).unwrap();
+        assert!(objects.targets().is_empty());
+        assert!(objects.get_target_by_name("Renamed").is_none());
+
+        objects.insert(id.clone(), target);
+        assert_eq!(objects.targets()[0].id, id);
+
+        let file = objects.files()[0].id.clone();
+        let parent = objects.get_fs_reference_parent(&file).unwrap().id;
+        objects.remove(&parent);
+        assert!(objects.get_fs_reference_parent(&file).is_none());
+    }
+
+    #[test]
+    fn preserves_source_order() {
+        let mut project = test_demo_file!(demo1);
+        let path = format!("{}/tests/samples/demo1.pbxproj", env!("CARGO_MANIFEST_DIR"));
+        let content = std::fs::read_to_string(path).unwrap();
+        let source_ids = content
+            .lines()
+            .filter(|l| l.starts_with("\t\t") && !l.starts_with("\t\t\t"))
+            .flat_map(|l| l.trim().split(' ').next())
+            .filter(|id| id.len() == 24)
+            .collect::<Vec<_>>();
+        let ids = project.keys().cloned().collect::<Vec<_>>();
+        assert_eq!(ids, source_ids);
+
+        let settings = project
+            .values()
+            .find_map(|v| v.get_object("buildSettings"))
+            .unwrap();
+        assert_eq!(
+            settings.keys().take(3).collect::<Vec<_>>(),
+            vec![
+                "ALWAYS_SEARCH_USER_PATHS",
+                "CLANG_ANALYZER_NONNULL",
+                "CLANG_ANALYZER_NUMBER_OBJECT_CONVERSION"
+            ]
+        );
+
+        // Replaced objects keep their position, reinserted ones are appended
+        let objects = project.objects_mut();
+        let object = objects.remove(&ids[1]).unwrap();
+        objects.insert(ids[1].clone(), object);
+        assert_eq!(objects.keys().last(), Some(&ids[1]));
+        assert!(objects
+            .insert(ids[0].clone(), PBXHashMap::default())
+            .is_some());
+        assert_eq!(objects.keys().next(), Some(&ids[0]));
+    }
+
+    #[test]
+    fn keeps_order_after_removal() {
+        let mut project = test_demo_file!(demo4);
+        let objects = project.objects_mut();
+        let ids = objects.keys().cloned().collect::<Vec<_>>();
+        let targets = objects.targets().len();
+        let files = objects.files().len();
+
+        let removed = ids.iter().step_by(3).cloned().collect::<Vec<_>>();
+        let (first, rest) = removed.split_at(removed.len() / 2);
+        for id in first {
+            objects.remove(id).unwrap();
+        }
+        objects.remove_all(&rest.iter().cloned().collect());
+
+        let expected = ids
+            .iter()
+            .filter(|id| !removed.contains(*id))
+            .collect::<Vec<_>>();
+        assert_eq!(objects.keys().collect::<Vec<_>>(), expected);
+        assert!(objects.targets().len() <= targets);
+        assert!(objects.files().len() < files);
+        assert!(objects
+            .files()
+            .iter()
+            .all(|file| !removed.contains(&file.id)));
+    }
+
+    #[test]
+    fn main_group() {
+        let project = test_demo_file!(demo1);
+        let main_group = project.root_group().id;
+        assert!(project.is_main_group(&main_group));
+        assert!(project
+            .groups()
+            .iter()
+            .filter(|group| group.id != main_group)
+            .all(|group| !project.is_main_group(&group.id)));
+        assert!(!project.is_main_group(&project.files()[0].id));
+    }
+}
@@ -30,17 +30,14 @@ impl<'a> AsPBXObject<'a> for PBXContainerItemProxy<'a> {
         Ok(Self {
             id,
             container_portal_reference: value.try_get_string("containerPortal")?,
-            proxy_type: value
-                .get_value("proxyType")
-                .map(|v| v.try_into().ok())
-                .flatten(),
+            proxy_type: value.get_value("proxyType").and_then(|v| v.try_into().ok()),
             remote_global_id_reference: value.get_string("remoteGlobalIdString"),
             remote_info: value.get_string("remoteInfo"),
         })
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 /// [`PBXContainerItemProxy`] Type
 pub enum PBXProxyType {
     /// Native Target
@@ -57,14 +54,25 @@ impl TryFrom<&PBXValue> for PBXProxyType {
     fn try_from(value: &PBXValue) -> Result<Self, Self::Error> {
         Ok(
             match value
-                .as_number()
+                .as_str()
                 .ok_or_else(|| anyhow::anyhow!("No pbx product type foudn"))?
+                .parse::<u8>()?
             {
                 1 => Self::NativeTarget,
                 2 => Self::Reference,
-                o => Self::Other(*o as u8),
+                o => Self::Other(o),
             },
         )
     }
 }
 
+impl From<&PBXProxyType> for PBXValue {
+    fn from(value: &PBXProxyType) -> Self {
+        let number = match value {
+            PBXProxyType::NativeTarget => 1,
+            PBXProxyType::Reference => 2,
+            PBXProxyType::Other(o) => *o as isize,
+        };
+        PBXValue::from(number)
+    }
+}
@@ -1,11 +1,12 @@
 use derive_is_enum_variant::is_enum_variant;
 
-#[derive(Clone, Debug, PartialEq, Eq, is_enum_variant, Ord, PartialOrd)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, is_enum_variant, Ord, PartialOrd)]
 /// [`PBXFSReference`] abstraction kind
 ///
 /// [`PBXFSReference`]: crate::pbxproj::PBXFSReference
 pub enum PBXFSReferenceKind {
     /// File Group
+    #[default]
     FileGroup,
     /// Version Group
     VersionGroup,
@@ -19,7 +20,7 @@ impl PBXFSReferenceKind {
     /// Return string representation compatible with pbxproj
     pub fn as_isa(&self) -> &str {
         match self {
-            PBXFSReferenceKind::FileGroup => "PBXFileGroup",
+            PBXFSReferenceKind::FileGroup => "PBXGroup",
             PBXFSReferenceKind::VersionGroup => "XCVersionGroup",
             PBXFSReferenceKind::VariantGroup => "PBXVariantGroup",
             PBXFSReferenceKind::File => "PBXFileReference",
@@ -30,9 +31,3 @@ impl PBXFSReferenceKind {
         self.is_file_group() || self.is_version_group() || self.is_variant_group()
     }
 }
-
-impl Default for PBXFSReferenceKind {
-    fn default() -> Self {
-        Self::FileGroup
-    }
-}
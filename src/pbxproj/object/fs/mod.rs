@@ -7,7 +7,7 @@ use anyhow::Result;
 pub use kind::*;
 pub use source_tree::*;
 
-// mod full_path;
+mod full_path;
 // use crate::xcode::xcode_file_type;
 
 /// Abstraction over `PBXFileReference`, `PBXGroup`, `PBXVariantGroup`, and `XCVersionGroup`
@@ -26,21 +26,21 @@ pub struct PBXFSReference<'a> {
     /// Element uses tabs.
     pub uses_tabs: Option<bool>,
     /// Element indent width.
-    pub indent_width: Option<&'a isize>,
+    pub indent_width: Option<isize>,
     /// Element tab width.
-    pub tab_width: Option<&'a isize>,
+    pub tab_width: Option<isize>,
     /// Element wraps lines.
     pub wraps_lines: Option<bool>,
     /// Element Kind.
     pub kind: PBXFSReferenceKind,
     /// Text encoding of file content (only relevant to PBXFileReference)
-    pub file_encoding: Option<&'a isize>,
+    pub file_encoding: Option<isize>,
     /// User-specified file type. use `last_known_file_type` instead. (only relevant to PBXFileReference)
     pub explicit_file_type: Option<&'a String>,
     /// Derived file type. For a file named "foo.swift" this value would be "sourcecode.swift" (only relevant to PBXFileReference)
     pub last_known_file_type: Option<&'a String>,
     /// Line ending type for the file (only relevant to PBXFileReference)
-    pub line_ending: Option<&'a isize>,
+    pub line_ending: Option<isize>,
     /// Legacy programming language identifier (only relevant to PBXFileReference)
     pub language_specification_identifier: Option<&'a String>,
     /// Programming language identifier (only relevant to PBXFileReference)
@@ -108,17 +108,17 @@ impl<'a> AsPBXObject<'a> for PBXFSReference<'a> {
                 .get_string("sourceTree")
                 .map(|s| s.as_str().into())
                 .unwrap_or_default(),
-            include_in_index: value.get_number("includeInIndex").map(|v| v == &1),
-            uses_tabs: value.get_number("usesTabs").map(|v| v == &1),
-            indent_width: value.get_number("indentWidth"),
-            tab_width: value.get_number("tabWidth"),
-            wraps_lines: value.get_number("wrapsLines").map(|v| v == &1),
+            include_in_index: value.get_flag("includeInIndex"),
+            uses_tabs: value.get_flag("usesTabs"),
+            indent_width: value.get_str("indentWidth").and_then(|v| v.parse().ok()),
+            tab_width: value.get_str("tabWidth").and_then(|v| v.parse().ok()),
+            wraps_lines: value.get_flag("wrapsLines"),
             current_version_reference: value.get_string("currentVersion"),
             parent: None,
-            file_encoding: value.get_number("fileEncoding"),
+            file_encoding: value.get_str("fileEncoding").and_then(|v| v.parse().ok()),
             explicit_file_type: value.get_string("explicitFileType"),
             last_known_file_type: value.get_string("lastKnownFileType"),
-            line_ending: value.get_number("lineEnding"),
+            line_ending: value.get_str("lineEnding").and_then(|v| v.parse().ok()),
             language_specification_identifier: value.get_string("languageSpecificationIdentifier"),
             xc_language_specification_identifier: value
                 .get_string("xcLanguageSpecificationIdentifier"),
@@ -0,0 +1,94 @@
+use crate::pbxproj::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Secondary indexes of [`PBXObjectCollection`], kept in sync on insertion and removal.
+#[derive(Default, Debug)]
+pub(crate) struct PBXObjectIndex {
+    /// Object ids by isa
+    isa: BTreeMap<String, BTreeSet<String>>,
+    /// Object ids by name, or by path for objects without a name
+    names: HashMap<String, BTreeSet<String>>,
+    /// Parent group id by child id
+    parents: HashMap<String, String>,
+}
+
+impl PBXObjectIndex {
+    /// Index object with a given id
+    pub(crate) fn insert(&mut self, id: &str, object: &PBXHashMap) {
+        if let Some(kind) = object.get_kind("isa") {
+            self.isa
+                .entry(kind.to_string())
+                .or_default()
+                .insert(id.to_string());
+        }
+
+        if let Some(name) = name_or_path(object) {
+            self.names
+                .entry(name.to_string())
+                .or_default()
+                .insert(id.to_string());
+        }
+
+        for child in children(object) {
+            self.parents.insert(child.to_string(), id.to_string());
+        }
+    }
+
+    /// Remove object with a given id from the index
+    pub(crate) fn remove(&mut self, id: &str, object: &PBXHashMap) {
+        if let Some(kind) = object.get_kind("isa") {
+            let isa = kind.to_string();
+            if let Some(ids) = self.isa.get_mut(&isa) {
+                ids.remove(id);
+                if ids.is_empty() {
+                    self.isa.remove(&isa);
+                }
+            }
+        }
+
+        if let Some(name) = name_or_path(object) {
+            if let Some(ids) = self.names.get_mut(name) {
+                ids.remove(id);
+                if ids.is_empty() {
+                    self.names.remove(name);
+                }
+            }
+        }
+
+        for child in children(object) {
+            if self.parents.get(child).map(String::as_str) == Some(id) {
+                self.parents.remove(child);
+            }
+        }
+    }
+
+    /// Get object kinds with ids of objects of each kind
+    pub(crate) fn kinds(&self) -> impl Iterator<Item = (PBXObjectKind, &BTreeSet<String>)> {
+        self.isa
+            .iter()
+            .map(|(isa, ids)| (PBXObjectKind::from(isa.as_str()), ids))
+    }
+
+    /// Get ids of objects with a given name, or path for objects without a name
+    pub(crate) fn by_name(&self, name: &str) -> impl Iterator<Item = &String> {
+        self.names.get(name).into_iter().flatten()
+    }
+
+    /// Get id of the group containing a given child
+    pub(crate) fn parent(&self, id: &str) -> Option<&String> {
+        self.parents.get(id)
+    }
+}
+
+fn name_or_path(object: &PBXHashMap) -> Option<&String> {
+    object
+        .get_string("name")
+        .or_else(|| object.get_string("path"))
+}
+
+fn children(object: &PBXHashMap) -> Vec<&String> {
+    object
+        .get_vec("children")
+        .map(|v| v.as_vec_strings())
+        .unwrap_or_default()
+}
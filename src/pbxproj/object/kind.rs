@@ -4,9 +4,10 @@ use enum_as_inner::EnumAsInner;
 use super::{PBXBuildPhaseKind, PBXFSReferenceKind};
 
 /// Representation of all Target kinds
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, is_enum_variant)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, is_enum_variant)]
 pub enum PBXTargetKind {
     /// A build target that produces a binary content (application or library).
+    #[default]
     Native,
     /// A build target that according to Xcode is an "External Build System".
     Legacy,
@@ -14,7 +15,7 @@ pub enum PBXTargetKind {
     Aggregate,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, EnumAsInner)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, EnumAsInner)]
 /// Pbxproj object kinds
 pub enum PBXObjectKind {
     /// An abstraction over targets, including:
@@ -145,9 +146,9 @@ impl From<&str> for PBXObjectKind {
     }
 }
 
-impl ToString for PBXObjectKind {
-    fn to_string(&self) -> String {
-        match self {
+impl std::fmt::Display for PBXObjectKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
             Self::PBXBuildFile => "PBXBuildFile",
             Self::PBXProject => "PBXProject",
             Self::XCConfigurationList => "XCConfigurationList",
@@ -165,7 +166,7 @@ impl ToString for PBXObjectKind {
             },
             PBXObjectKind::PBXBuildPhase(kind) => kind.as_isa(),
             Self::Unknown(str) => str,
-        }
-        .into()
+        };
+        write!(f, "{value}")
     }
 }
@@ -0,0 +1,316 @@
+use crate::pbxproj::*;
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+/// Inclusion of a file in a target build phase
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PBXFileMembership<'a> {
+    /// Target id
+    pub target_id: &'a str,
+    /// Target name
+    pub target_name: Option<&'a String>,
+    /// Build phase id
+    pub build_phase_id: &'a str,
+    /// Build phase kind
+    pub build_phase_kind: PBXBuildPhaseKind,
+    /// Build file id
+    pub build_file_id: &'a str,
+    /// Build file settings, i.e. `COMPILER_FLAGS` or `ATTRIBUTES`
+    pub settings: Option<&'a PBXValue>,
+}
+
+impl<'a> PBXFileMembership<'a> {
+    /// Get the target file is included in
+    pub fn target(&self, objects: &'a PBXObjectCollection) -> Option<PBXTarget<'a>> {
+        objects.get_target(self.target_id)
+    }
+
+    /// Get the build phase file is included in
+    pub fn build_phase(&self, objects: &'a PBXObjectCollection) -> Option<PBXBuildPhase<'a>> {
+        objects.get_build_phase(self.build_phase_id)
+    }
+
+    /// Get the build file referencing the file
+    pub fn build_file(&self, objects: &'a PBXObjectCollection) -> Option<PBXBuildFile<'a>> {
+        objects.get_build_file(self.build_file_id)
+    }
+}
+
+/// Index of target and build phase membership of project files, by file id and full path.
+///
+/// Files referenced through a variant or version group are members wherever the group is.
+/// Paths are normalized lexically, so `./` and `..` components, repeated separators and paths
+/// relative to source root all match the same file. Symbolic links are not resolved.
+#[derive(Debug, Default)]
+pub struct PBXFileMembershipIndex<'a> {
+    source_root: PathBuf,
+    memberships: HashMap<&'a str, Vec<PBXFileMembership<'a>>>,
+    paths: HashMap<&'a str, PathBuf>,
+    ids: HashMap<PathBuf, &'a str>,
+    files: Vec<&'a str>,
+    products: HashSet<&'a str>,
+}
+
+impl<'a> PBXFileMembershipIndex<'a> {
+    /// Create new index of objects, resolving file paths against source_root.
+    pub fn new<P: AsRef<Path>>(objects: &'a PBXObjectCollection, source_root: P) -> Self {
+        let mut index = Self {
+            source_root: normalize_path(source_root.as_ref()),
+            ..Default::default()
+        };
+        let source_root = index.source_root.clone();
+        let source_root = source_root.as_path();
+        let get_ids = |value: &'a PBXHashMap, key: &str| {
+            value
+                .get_vec(key)
+                .map(|v| v.as_vec_strings())
+                .unwrap_or_default()
+        };
+
+        for (id, value) in objects.iter() {
+            let kind = match value.get_kind("isa") {
+                Some(kind) => kind,
+                None => continue,
+            };
+
+            if kind.as_pbxfs_reference().map(|k| k.is_file()) == Some(true) {
+                index.files.push(id);
+            }
+
+            if kind.is_pbx_project() {
+                if let Some(main_group) = value.get_string("mainGroup") {
+                    index.index_paths(objects, main_group, source_root, source_root);
+                }
+            }
+
+            if !kind.is_pbx_target() {
+                continue;
+            }
+
+            if let Some(product) = value.get_string("productReference") {
+                index.products.insert(product);
+            }
+
+            for (phase_id, phase) in get_ids(value, "buildPhases")
+                .into_iter()
+                .flat_map(|id| Some((id, objects.get_object(id)?)))
+            {
+                let build_phase_kind =
+                    match phase.get_kind("isa").and_then(|k| k.as_pbx_build_phase()) {
+                        Some(kind) => kind,
+                        None => continue,
+                    };
+
+                for (build_file_id, build_file) in get_ids(phase, "files")
+                    .into_iter()
+                    .flat_map(|id| Some((id, objects.get_object(id)?)))
+                {
+                    let file_id = match build_file.get_string("fileRef") {
+                        Some(file_id) => file_id,
+                        None => continue,
+                    };
+                    let membership = PBXFileMembership {
+                        target_id: id,
+                        target_name: value.get_string("name"),
+                        build_phase_id: phase_id,
+                        build_phase_kind: build_phase_kind.clone(),
+                        build_file_id,
+                        settings: build_file.get_value("settings"),
+                    };
+
+                    let children = objects
+                        .get_object(file_id)
+                        .map(|file| get_ids(file, "children"))
+                        .unwrap_or_default();
+                    for file_id in children.into_iter().chain([file_id]) {
+                        index
+                            .memberships
+                            .entry(file_id)
+                            .or_default()
+                            .push(membership.clone());
+                    }
+                }
+            }
+        }
+
+        index.files.sort_unstable();
+        index
+    }
+
+    /// Get memberships of a file with a given id
+    pub fn get(&self, file_id: &str) -> &[PBXFileMembership<'a>] {
+        self.memberships
+            .get(file_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Get memberships of a file with a given path, either full or relative to source root
+    pub fn get_by_path<P: AsRef<Path>>(&self, path: P) -> &[PBXFileMembership<'a>] {
+        self.file_id(path)
+            .map(|id| self.get(id))
+            .unwrap_or_default()
+    }
+
+    /// Get id of a file with a given path, either full or relative to source root
+    pub fn file_id<P: AsRef<Path>>(&self, path: P) -> Option<&'a str> {
+        let path = normalize_path(&self.source_root.join(path));
+        self.ids.get(&path).copied()
+    }
+
+    /// Get full path of a file with a given id
+    pub fn path(&self, file_id: &str) -> Option<&Path> {
+        self.paths.get(file_id).map(PathBuf::as_path)
+    }
+
+    /// Get ids of files that belong to no target, excluding target products.
+    pub fn orphan_files(&self) -> Vec<&'a str> {
+        self.files
+            .iter()
+            .filter(|id| !self.memberships.contains_key(*id) && !self.products.contains(*id))
+            .copied()
+            .collect()
+    }
+
+    /// Resolve paths of fs reference and its children, given path of the parent group.
+    ///
+    /// NOTE: References relative to build products, sdk or developer directory are skipped.
+    fn index_paths(
+        &mut self,
+        objects: &'a PBXObjectCollection,
+        id: &'a str,
+        parent: &Path,
+        source_root: &Path,
+    ) {
+        let value = match objects.get_object(id) {
+            Some(value) => value,
+            None => return,
+        };
+        let path = value.get_string("path").map(String::as_str);
+        let source_tree = value
+            .get_string("sourceTree")
+            .map(|s| PBXSourceTree::from(s.as_str()))
+            .unwrap_or_default();
+
+        let full_path = match (source_tree, path) {
+            (PBXSourceTree::Absolute, Some(path)) => PathBuf::from(path),
+            (PBXSourceTree::SourceRoot, Some(path)) => source_root.join(path),
+            (PBXSourceTree::SourceRoot, None) => source_root.to_path_buf(),
+            (PBXSourceTree::Group | PBXSourceTree::None, Some(path)) => parent.join(path),
+            (PBXSourceTree::Group | PBXSourceTree::None, None) => parent.to_path_buf(),
+            _ => return,
+        };
+        let full_path = normalize_path(&full_path);
+
+        if let Some(children) = value.get_vec("children") {
+            for child in children.as_vec_strings() {
+                self.index_paths(objects, child, &full_path, source_root);
+            }
+        }
+
+        // Groups without path share path of their parent
+        if path.is_some() {
+            self.ids.insert(full_path.clone(), id);
+        }
+        self.paths.insert(id, full_path);
+    }
+}
+
+/// Normalize path lexically, dropping `.` components and resolving `..` against preceding ones.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => normalized.push(component),
+            },
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+impl PBXObjectCollection {
+    /// Build index of target and build phase membership of files, resolving file paths
+    /// against source_root.
+    pub fn file_membership_index<P: AsRef<Path>>(
+        &self,
+        source_root: P,
+    ) -> PBXFileMembershipIndex<'_> {
+        PBXFileMembershipIndex::new(self, source_root)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbxproj::test_demo_file;
+
+    #[test]
+    fn lookup_by_path() {
+        let project = test_demo_file!(demo1);
+        let index = project.file_membership_index("/root");
+        let memberships = index.get_by_path("/root/Source/Views/GuessView.swift");
+
+        assert_eq!(memberships.len(), 1);
+        assert_eq!(memberships[0].target_name.unwrap(), "Wordle");
+        assert_eq!(memberships[0].build_phase_kind, PBXBuildPhaseKind::Sources);
+        assert_eq!(
+            memberships[0].target(&project).unwrap().name.unwrap(),
+            "Wordle"
+        );
+
+        let id = index.file_id("/root/Source/Views/GuessView.swift").unwrap();
+        assert_eq!(index.get(id), memberships);
+        assert!(index.get_by_path("/root/Source/Missing.swift").is_empty());
+
+        for path in [
+            "Source/Views/GuessView.swift",
+            "./Source/Views/GuessView.swift",
+            "/root//Source/./Views/GuessView.swift",
+            "/root/Source/Models/../Views/GuessView.swift",
+        ] {
+            assert_eq!(index.file_id(path), Some(id), "{path}");
+        }
+        let index = project.file_membership_index("/root/./");
+        assert_eq!(index.file_id("Source/Views/GuessView.swift"), Some(id));
+    }
+
+    #[test]
+    fn settings_and_variant_groups() {
+        let project = test_demo_file!(demo8);
+        let index = project.file_membership_index("/root");
+        let memberships = index.get_by_path("/root/test/ViewController.m");
+        let settings = memberships[0].settings.unwrap().as_object().unwrap();
+        assert_eq!(
+            settings.get_string("COMPILER_FLAGS").unwrap(),
+            "-flag -flag2"
+        );
+
+        // Localized storyboard is a member through its PBXVariantGroup
+        let memberships = index.get_by_path("/root/test/Base.lproj/Main.storyboard");
+        assert_eq!(
+            memberships[0].build_phase_kind,
+            PBXBuildPhaseKind::Resources
+        );
+    }
+
+    #[test]
+    fn orphan_files() {
+        let project = test_demo_file!(demo8);
+        let index = project.file_membership_index("/root");
+        let orphans = index
+            .orphan_files()
+            .into_iter()
+            .flat_map(|id| index.path(id))
+            .collect::<Vec<_>>();
+
+        assert!(orphans.contains(&Path::new("/root/test/Info.plist")));
+        assert!(!orphans.contains(&Path::new("/root/test/ViewController.m")));
+    }
+}
@@ -3,14 +3,20 @@ mod project;
 mod swift_package;
 mod target;
 
+mod cache;
 mod collection;
 mod container_item_proxy;
 mod fs;
+mod index;
 mod kind;
+mod membership;
+mod owned;
 mod product_type;
 
 pub use fs::*;
 pub use kind::*;
+pub use membership::*;
+pub use owned::*;
 pub use product_type::*;
 
 pub use build::*;
@@ -20,4 +26,6 @@ pub use project::*;
 pub use swift_package::*;
 pub use target::*;
 
+pub use cache::*;
 pub use collection::*;
+pub(crate) use index::PBXObjectIndex;
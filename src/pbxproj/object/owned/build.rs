@@ -0,0 +1,271 @@
+use super::*;
+
+/// Owned [`XCConfigurationList`]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct XCConfigurationListData {
+    /// Build configuration ids.
+    pub build_configurations: Vec<String>,
+    /// Element default configuration is visible.
+    pub default_configuration_is_visible: bool,
+    /// Element default configuration name
+    pub default_configuration_name: Option<String>,
+    /// Keys that aren't modeled
+    pub extra: PBXHashMap,
+}
+
+impl XCConfigurationListData {
+    /// Create new configuration list of given build configuration ids
+    pub fn new(build_configurations: Vec<String>) -> Self {
+        Self {
+            build_configurations,
+            ..Default::default()
+        }
+    }
+}
+
+impl TryFrom<PBXHashMap> for XCConfigurationListData {
+    type Error = anyhow::Error;
+
+    fn try_from(mut value: PBXHashMap) -> Result<Self> {
+        let kind = value.try_remove_kind("isa")?;
+        if kind != PBXObjectKind::XCConfigurationList {
+            anyhow::bail!("expected XCConfigurationList, got {kind}");
+        }
+
+        Ok(Self {
+            build_configurations: take_strings(&mut value, "buildConfigurations")?,
+            default_configuration_is_visible: take_flag(
+                &mut value,
+                "defaultConfigurationIsVisible",
+            )
+            .unwrap_or_default(),
+            default_configuration_name: take_str(&mut value, "defaultConfigurationName"),
+            extra: value,
+        })
+    }
+}
+
+impl From<XCConfigurationListData> for PBXHashMap {
+    fn from(value: XCConfigurationListData) -> Self {
+        let fields = [
+            (
+                "buildConfigurations",
+                Some(value.build_configurations.into()),
+            ),
+            (
+                "defaultConfigurationIsVisible",
+                Some(flag(value.default_configuration_is_visible)),
+            ),
+            (
+                "defaultConfigurationName",
+                value.default_configuration_name.map(Into::into),
+            ),
+        ];
+
+        object_map(PBXObjectKind::XCConfigurationList, fields, value.extra)
+    }
+}
+
+/// Owned [`XCBuildConfiguration`]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct XCBuildConfigurationData {
+    /// Configuration name, i.e. `Debug`
+    pub name: String,
+    /// Build settings
+    pub build_settings: PBXHashMap,
+    /// Base xcconfig file reference id
+    pub base_configuration_reference: Option<String>,
+    /// Keys that aren't modeled
+    pub extra: PBXHashMap,
+}
+
+impl XCBuildConfigurationData {
+    /// Create new build configuration with a given name and no build settings
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl TryFrom<PBXHashMap> for XCBuildConfigurationData {
+    type Error = anyhow::Error;
+
+    fn try_from(mut value: PBXHashMap) -> Result<Self> {
+        let kind = value.try_remove_kind("isa")?;
+        if kind != PBXObjectKind::XCBuildConfiguration {
+            anyhow::bail!("expected XCBuildConfiguration, got {kind}");
+        }
+
+        Ok(Self {
+            name: try_take_str(&mut value, "name")?,
+            build_settings: take_object(&mut value, "buildSettings").unwrap_or_default(),
+            base_configuration_reference: take_str(&mut value, "baseConfigurationReference"),
+            extra: value,
+        })
+    }
+}
+
+impl From<XCBuildConfigurationData> for PBXHashMap {
+    fn from(value: XCBuildConfigurationData) -> Self {
+        let fields = [
+            ("name", Some(value.name.into())),
+            ("buildSettings", Some(value.build_settings.into())),
+            (
+                "baseConfigurationReference",
+                value.base_configuration_reference.map(Into::into),
+            ),
+        ];
+
+        object_map(PBXObjectKind::XCBuildConfiguration, fields, value.extra)
+    }
+}
+
+/// Owned [`PBXBuildPhase`]
+///
+/// Kind specific keys, i.e. `shellScript` or `dstPath`, are kept in `extra`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PBXBuildPhaseData {
+    /// Build phase kind
+    pub kind: PBXBuildPhaseKind,
+    /// Build phase name
+    pub name: Option<String>,
+    /// Element build action mask.
+    pub build_action_mask: isize,
+    /// Build file ids.
+    pub files: Vec<String>,
+    /// Element run only for deployment post processing value.
+    pub run_only_for_deployment_postprocessing: bool,
+    /// Keys that aren't modeled
+    pub extra: PBXHashMap,
+}
+
+impl PBXBuildPhaseData {
+    /// Create new empty build phase of a given kind
+    pub fn new(kind: PBXBuildPhaseKind) -> Self {
+        Self {
+            kind,
+            name: None,
+            build_action_mask: 2_147_483_647,
+            files: vec![],
+            run_only_for_deployment_postprocessing: false,
+            extra: Default::default(),
+        }
+    }
+}
+
+impl TryFrom<PBXHashMap> for PBXBuildPhaseData {
+    type Error = anyhow::Error;
+
+    fn try_from(mut value: PBXHashMap) -> Result<Self> {
+        let kind = value
+            .try_remove_kind("isa")?
+            .into_pbx_build_phase()
+            .map_err(|k| anyhow::anyhow!("expected build phase, got {k}"))?;
+        let mut phase = Self::new(kind);
+
+        phase.name = take_str(&mut value, "name");
+        if let Some(mask) = take_with(
+            &mut value,
+            "buildActionMask",
+            |mask| mask.as_str()?.parse().ok(),
+            |&mask: &isize| mask.into(),
+        ) {
+            phase.build_action_mask = mask;
+        }
+        phase.files = take_strings(&mut value, "files")?;
+        phase.run_only_for_deployment_postprocessing =
+            take_flag(&mut value, "runOnlyForDeploymentPostprocessing").unwrap_or_default();
+        phase.extra = value;
+
+        Ok(phase)
+    }
+}
+
+impl From<PBXBuildPhaseData> for PBXHashMap {
+    fn from(value: PBXBuildPhaseData) -> Self {
+        let fields = [
+            ("name", value.name.map(Into::into)),
+            ("buildActionMask", Some(value.build_action_mask.into())),
+            ("files", Some(value.files.into())),
+            (
+                "runOnlyForDeploymentPostprocessing",
+                Some(flag(value.run_only_for_deployment_postprocessing)),
+            ),
+        ];
+
+        object_map(
+            PBXObjectKind::PBXBuildPhase(value.kind),
+            fields,
+            value.extra,
+        )
+    }
+}
+
+/// Owned [`PBXBuildFile`]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PBXBuildFileData {
+    /// File reference id.
+    pub file_ref: Option<String>,
+    /// Swift package product dependency id.
+    pub product_ref: Option<String>,
+    /// Element settings, i.e. `COMPILER_FLAGS` or `ATTRIBUTES`
+    pub settings: Option<PBXHashMap>,
+    /// Platform filter attribute.
+    pub platform_filter: Option<String>,
+    /// Keys that aren't modeled
+    pub extra: PBXHashMap,
+}
+
+impl PBXBuildFileData {
+    /// Create new build file referencing a given file id
+    pub fn new<S: Into<String>>(file_ref: S) -> Self {
+        Self {
+            file_ref: Some(file_ref.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Get typed settings
+    pub fn typed_settings(&self) -> PBXBuildFileSettings {
+        self.settings.as_ref().map(Into::into).unwrap_or_default()
+    }
+
+    /// Set typed settings, dropping `settings` once empty
+    pub fn set_typed_settings(&mut self, settings: PBXBuildFileSettings) {
+        self.settings = (!settings.is_empty()).then(|| settings.into());
+    }
+}
+
+impl TryFrom<PBXHashMap> for PBXBuildFileData {
+    type Error = anyhow::Error;
+
+    fn try_from(mut value: PBXHashMap) -> Result<Self> {
+        let kind = value.try_remove_kind("isa")?;
+        if kind != PBXObjectKind::PBXBuildFile {
+            anyhow::bail!("expected PBXBuildFile, got {kind}");
+        }
+
+        Ok(Self {
+            file_ref: take_str(&mut value, "fileRef"),
+            product_ref: take_str(&mut value, "productRef"),
+            settings: take_object(&mut value, "settings"),
+            platform_filter: take_str(&mut value, "platformFilter"),
+            extra: value,
+        })
+    }
+}
+
+impl From<PBXBuildFileData> for PBXHashMap {
+    fn from(value: PBXBuildFileData) -> Self {
+        let fields = [
+            ("fileRef", value.file_ref.map(Into::into)),
+            ("productRef", value.product_ref.map(Into::into)),
+            ("settings", value.settings.map(Into::into)),
+            ("platformFilter", value.platform_filter.map(Into::into)),
+        ];
+
+        object_map(PBXObjectKind::PBXBuildFile, fields, value.extra)
+    }
+}
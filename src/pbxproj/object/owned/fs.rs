@@ -0,0 +1,102 @@
+use super::*;
+
+/// Owned [`PBXFSReference`], a file reference or a group
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PBXFSReferenceData {
+    /// Reference kind
+    pub kind: PBXFSReferenceKind,
+    /// Element name.
+    pub name: Option<String>,
+    /// Element path.
+    pub path: Option<String>,
+    /// Element source tree.
+    pub source_tree: PBXSourceTree,
+    /// Children ids (relevant only for groups)
+    pub children: Vec<String>,
+    /// Element explicit file type.
+    pub explicit_file_type: Option<String>,
+    /// Element last known file type.
+    pub last_known_file_type: Option<String>,
+    /// Element include in index.
+    pub include_in_index: Option<bool>,
+    /// Keys that aren't modeled
+    pub extra: PBXHashMap,
+}
+
+impl PBXFSReferenceData {
+    /// Create new file reference with a path relative to its group
+    pub fn file<S: Into<String>>(path: S) -> Self {
+        Self {
+            kind: PBXFSReferenceKind::File,
+            path: Some(path.into()),
+            source_tree: PBXSourceTree::Group,
+            ..Default::default()
+        }
+    }
+
+    /// Create new group with a given name and no path
+    pub fn group<S: Into<String>>(name: S, children: Vec<String>) -> Self {
+        Self {
+            kind: PBXFSReferenceKind::FileGroup,
+            name: Some(name.into()),
+            source_tree: PBXSourceTree::Group,
+            children,
+            ..Default::default()
+        }
+    }
+}
+
+impl TryFrom<PBXHashMap> for PBXFSReferenceData {
+    type Error = anyhow::Error;
+
+    fn try_from(mut value: PBXHashMap) -> Result<Self> {
+        let kind = value
+            .try_remove_kind("isa")?
+            .into_pbxfs_reference()
+            .map_err(|k| anyhow::anyhow!("expected file reference or group, got {k}"))?;
+
+        Ok(Self {
+            kind,
+            name: take_str(&mut value, "name"),
+            path: take_str(&mut value, "path"),
+            source_tree: take_str(&mut value, "sourceTree")
+                .map(PBXSourceTree::from)
+                .unwrap_or_default(),
+            children: take_strings(&mut value, "children")?,
+            explicit_file_type: take_str(&mut value, "explicitFileType"),
+            last_known_file_type: take_str(&mut value, "lastKnownFileType"),
+            include_in_index: take_flag(&mut value, "includeInIndex"),
+            extra: value,
+        })
+    }
+}
+
+impl From<PBXFSReferenceData> for PBXHashMap {
+    fn from(value: PBXFSReferenceData) -> Self {
+        let source_tree = match value.source_tree {
+            PBXSourceTree::None => None,
+            source_tree => Some(source_tree.to_string().into()),
+        };
+        let fields = [
+            ("name", value.name.map(Into::into)),
+            ("path", value.path.map(Into::into)),
+            ("sourceTree", source_tree),
+            (
+                "children",
+                value.kind.is_group().then(|| value.children.into()),
+            ),
+            ("explicitFileType", value.explicit_file_type.map(Into::into)),
+            (
+                "lastKnownFileType",
+                value.last_known_file_type.map(Into::into),
+            ),
+            ("includeInIndex", value.include_in_index.map(flag)),
+        ];
+
+        object_map(
+            PBXObjectKind::PBXFSReference(value.kind),
+            fields,
+            value.extra,
+        )
+    }
+}
@@ -0,0 +1,315 @@
+//! Owned counterparts of pbxproj objects.
+//!
+//! Unlike borrowed views such as [`PBXTarget`], owned objects reference other objects by id,
+//! can be created from scratch, and outlive changes to [`PBXObjectCollection`]. They convert
+//! from raw objects with `TryFrom<PBXHashMap>` and back with `Into<PBXHashMap>`. Keys that
+//! aren't modeled, as well as values written differently than their fields would be, i.e.
+//! `name = 2024;` or `includeInIndex = YES;`, are kept in `extra`, so conversion
+//! round-trips without loss.
+mod build;
+mod fs;
+mod package;
+mod target;
+
+pub use build::*;
+pub use fs::*;
+pub use package::*;
+pub use target::*;
+
+use crate::pbxproj::*;
+use anyhow::Result;
+
+impl PBXObjectCollection {
+    /// Get owned copy of object with a given id
+    pub fn get_owned<T>(&self, id: &str) -> Result<T>
+    where
+        T: TryFrom<PBXHashMap, Error = anyhow::Error>,
+    {
+        self.get_object(id)
+            .ok_or_else(|| anyhow::anyhow!("{id:?} doesn't exists!"))?
+            .clone()
+            .try_into()
+    }
+}
+
+/// Read value with a given key, removing it only when writing the read value back
+/// reproduces it.
+///
+/// Values written differently, i.e. a quoted flag or a numeric lexeme, and values that can't
+/// be read at all stay in `extra`, so [`object_map`] can write them back as they were.
+fn take_with<T, R, W>(map: &mut PBXHashMap, key: &str, read: R, write: W) -> Option<T>
+where
+    R: FnOnce(&PBXValue) -> Option<T>,
+    W: FnOnce(&T) -> PBXValue,
+{
+    let raw = map.get_value(key)?;
+    let value = read(raw)?;
+    if &write(&value) == raw {
+        map.remove_value(key);
+    }
+    Some(value)
+}
+
+/// Read list of ids or strings with a given key
+fn take_strings(map: &mut PBXHashMap, key: &str) -> Result<Vec<String>> {
+    let read = |value: &PBXValue| {
+        value
+            .as_vec()?
+            .iter()
+            .map(|v| v.as_str().map(String::from))
+            .collect::<Option<Vec<_>>>()
+    };
+    match take_with(map, key, read, |strings| strings.clone().into()) {
+        Some(strings) => Ok(strings),
+        None if map.contains_key(key) => {
+            anyhow::bail!("expected {key} to be a list of strings, got {:?}", map[key])
+        }
+        None => Ok(vec![]),
+    }
+}
+
+/// Read string or number lexeme, see [`PBXValue::as_str`], with a given key
+fn take_str(map: &mut PBXHashMap, key: &str) -> Option<String> {
+    take_with(
+        map,
+        key,
+        |value| value.as_str().map(String::from),
+        |string| string.as_str().into(),
+    )
+}
+
+/// Read required string or number lexeme with a given key
+fn try_take_str(map: &mut PBXHashMap, key: &str) -> Result<String> {
+    take_str(map, key).ok_or_else(|| match map.get_value(key) {
+        Some(value) => anyhow::anyhow!("expected {key} to be str, got {value:?}"),
+        None => anyhow::anyhow!("{key} is not found!"),
+    })
+}
+
+/// Read object with a given key
+fn take_object(map: &mut PBXHashMap, key: &str) -> Option<PBXHashMap> {
+    take_with(
+        map,
+        key,
+        |value| value.as_object().cloned(),
+        |object| object.clone().into(),
+    )
+}
+
+/// Read flag, i.e. `includeInIndex = 0`, see [`PBXValue::as_flag`], with a given key
+fn take_flag(map: &mut PBXHashMap, key: &str) -> Option<bool> {
+    take_with(map, key, PBXValue::as_flag, |&value| flag(value))
+}
+
+/// Number flag value
+fn flag(value: bool) -> PBXValue {
+    PBXValue::from(value as isize)
+}
+
+/// Whether a raw value left in `extra` still means a given field value
+fn is_same_value(raw: &PBXValue, value: &PBXValue) -> bool {
+    match (raw, value) {
+        (PBXValue::Vec(raw), PBXValue::Vec(value)) => {
+            raw.len() == value.len()
+                && raw
+                    .iter()
+                    .zip(value.iter())
+                    .all(|(r, v)| is_same_value(r, v))
+        }
+        (raw, value) => {
+            let is_same_str = raw.as_str().is_some() && raw.as_str() == value.as_str();
+            // Flags are written as numbers, but can be read from `YES` or `"1"`
+            let is_same_flag = value.as_number().is_some()
+                && raw.as_flag().is_some()
+                && raw.as_flag() == value.as_flag();
+            raw == value || is_same_str || is_same_flag
+        }
+    }
+}
+
+/// Build raw object from isa, fields and extra keys, with keys sorted after isa as Xcode
+/// writes them.
+///
+/// Raw values left in `extra` by readers are written instead of fields that still mean the
+/// same, and kept when there is no field value.
+fn object_map<'a, I>(isa: PBXObjectKind, fields: I, mut extra: PBXHashMap) -> PBXHashMap
+where
+    I: IntoIterator<Item = (&'a str, Option<PBXValue>)>,
+{
+    let fields = fields
+        .into_iter()
+        .flat_map(|(key, value)| {
+            let value = match (extra.0.shift_remove(key), value) {
+                (Some(raw), Some(value)) if is_same_value(&raw, &value) => raw,
+                (raw, value) => value.or(raw)?,
+            };
+            Some((key.to_string(), value))
+        })
+        .collect::<Vec<_>>();
+    let mut fields = fields.into_iter().chain(extra.0).collect::<PBXHashMap>();
+    fields.0.sort_keys();
+
+    std::iter::once(("isa".to_string(), PBXValue::Kind(isa)))
+        .chain(fields.0)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbxproj::test_demo_file;
+
+    fn assert_round_trip(project: &PBXRootObject) {
+        for (id, object) in project.iter() {
+            let kind = object.get_kind("isa").unwrap();
+            let converted = if kind.is_pbx_target() {
+                PBXHashMap::from(project.get_owned::<PBXTargetData>(id).unwrap())
+            } else if kind.is_pbx_build_phase() {
+                project.get_owned::<PBXBuildPhaseData>(id).unwrap().into()
+            } else if kind.is_pbx_fsreference() {
+                project.get_owned::<PBXFSReferenceData>(id).unwrap().into()
+            } else {
+                match kind {
+                    PBXObjectKind::XCConfigurationList => project
+                        .get_owned::<XCConfigurationListData>(id)
+                        .unwrap()
+                        .into(),
+                    PBXObjectKind::XCBuildConfiguration => project
+                        .get_owned::<XCBuildConfigurationData>(id)
+                        .unwrap()
+                        .into(),
+                    PBXObjectKind::PBXBuildFile => {
+                        project.get_owned::<PBXBuildFileData>(id).unwrap().into()
+                    }
+                    PBXObjectKind::PBXTargetDependency => project
+                        .get_owned::<PBXTargetDependencyData>(id)
+                        .unwrap()
+                        .into(),
+                    PBXObjectKind::PBXContainerItemProxy => project
+                        .get_owned::<PBXContainerItemProxyData>(id)
+                        .unwrap()
+                        .into(),
+                    PBXObjectKind::XCRemoteSwiftPackageReference => project
+                        .get_owned::<XCRemoteSwiftPackageReferenceData>(id)
+                        .unwrap()
+                        .into(),
+                    PBXObjectKind::XCSwiftPackageProductDependency => project
+                        .get_owned::<XCSwiftPackageProductDependencyData>(id)
+                        .unwrap()
+                        .into(),
+                    _ => continue,
+                }
+            };
+            assert_eq!(&converted, object, "{id}");
+            assert_eq!(
+                converted.keys().collect::<Vec<_>>(),
+                object.keys().collect::<Vec<_>>(),
+                "{id}"
+            );
+        }
+    }
+
+    macro_rules! test_samples {
+        ($($name:ident),*) => {
+            $(#[test]
+                fn $name() {
+                    assert_round_trip(&test_demo_file!($name));
+                })*
+        };
+    }
+
+    test_samples![
+        demo1, demo2, demo3, demo4, demo5, demo6, demo7, demo8, demo9, demo10, demo11, demo12
+    ];
+
+    #[test]
+    fn lenient_values_round_trip() {
+        let number = |lexeme: &str| PBXValue::Number(PBXNumber::new(lexeme).unwrap());
+        let object = [
+            (
+                "isa",
+                PBXValue::Kind(PBXObjectKind::PBXFSReference(PBXFSReferenceKind::File)),
+            ),
+            ("includeInIndex", "YES".into()),
+            ("lastKnownFileType", "folder".into()),
+            ("name", number("2024")),
+            ("path", number("2024")),
+            ("sourceTree", "<group>".into()),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect::<PBXHashMap>();
+
+        let mut file = PBXFSReferenceData::try_from(object.clone()).unwrap();
+        assert_eq!(file.name.as_deref(), Some("2024"));
+        assert_eq!(file.path.as_deref(), Some("2024"));
+        assert_eq!(file.include_in_index, Some(true));
+
+        let converted = PBXHashMap::from(file.clone());
+        assert_eq!(converted, object);
+        assert!(converted.keys().eq(object.keys()));
+
+        file.name = Some("2025".into());
+        file.include_in_index = Some(false);
+        let converted = PBXHashMap::from(file);
+        assert_eq!(converted.get_string("name").unwrap(), "2025");
+        assert_eq!(converted.get_value("includeInIndex"), Some(&number("0")));
+        assert_eq!(converted.get_value("path"), Some(&number("2024")));
+
+        let mut object = object;
+        object.insert_value("includeInIndex", "maybe");
+        let file = PBXFSReferenceData::try_from(object.clone()).unwrap();
+        assert_eq!(file.include_in_index, None);
+        assert_eq!(PBXHashMap::from(file), object);
+    }
+
+    #[test]
+    fn build_target_in_memory() {
+        let mut project = test_demo_file!(demo1);
+        let objects = project.objects_mut();
+
+        let configurations = ["Debug", "Release"]
+            .into_iter()
+            .map(|name| {
+                let mut configuration = XCBuildConfigurationData::new(name);
+                configuration
+                    .build_settings
+                    .insert_value("PRODUCT_NAME", "$(TARGET_NAME)");
+                objects.push(configuration)
+            })
+            .collect::<Vec<_>>();
+        let mut list = XCConfigurationListData::new(configurations);
+        list.default_configuration_name = Some("Release".into());
+        let list = objects.push(list);
+
+        let file = objects.push(PBXFSReferenceData::file("Source/Tool.swift"));
+        let build_file = objects.push(PBXBuildFileData::new(file.clone()));
+        let mut sources = PBXBuildPhaseData::new(PBXBuildPhaseKind::Sources);
+        sources.files.push(build_file);
+        let sources = objects.push(sources);
+
+        let mut target = PBXTargetData::new("Tool", PBXProductType::CommandLineTool);
+        target.build_configuration_list = Some(list);
+        target.build_phases = vec![
+            sources,
+            objects.push(PBXBuildPhaseData::new(PBXBuildPhaseKind::Frameworks)),
+        ];
+        let id = objects.push(target);
+
+        let target = objects.get_target_by_name("Tool").unwrap();
+        assert_eq!(target.id, id);
+        assert_eq!(target.product_type, PBXProductType::CommandLineTool);
+        assert_eq!(target.build_phases.len(), 2);
+        assert_eq!(
+            target.build_phases[0].files[0].file.as_ref().unwrap().id,
+            file
+        );
+        let list = target.build_configuration_list.unwrap();
+        assert_eq!(list.build_configurations.len(), 2);
+        assert_eq!(list.default_configuration_name.unwrap(), "Release");
+
+        let owned = objects.get_owned::<PBXTargetData>(&id).unwrap();
+        assert_eq!(owned.name, "Tool");
+        assert!(objects.get_owned::<PBXTargetData>(&file).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+use super::*;
+
+/// Owned [`XCRemoteSwiftPackageReference`]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct XCRemoteSwiftPackageReferenceData {
+    /// Repository url.
+    pub repository_url: Option<String>,
+    /// Version rules.
+    pub requirement: Option<XCVersionRequirement>,
+    /// Keys that aren't modeled
+    pub extra: PBXHashMap,
+}
+
+impl XCRemoteSwiftPackageReferenceData {
+    /// Create new package reference of a given repository url and version rules
+    pub fn new<S: Into<String>>(repository_url: S, requirement: XCVersionRequirement) -> Self {
+        Self {
+            repository_url: Some(repository_url.into()),
+            requirement: Some(requirement),
+            extra: Default::default(),
+        }
+    }
+}
+
+impl TryFrom<PBXHashMap> for XCRemoteSwiftPackageReferenceData {
+    type Error = anyhow::Error;
+
+    fn try_from(mut value: PBXHashMap) -> Result<Self> {
+        let kind = value.try_remove_kind("isa")?;
+        if kind != PBXObjectKind::XCRemoteSwiftPackageReference {
+            anyhow::bail!("expected XCRemoteSwiftPackageReference, got {kind}");
+        }
+
+        Ok(Self {
+            repository_url: take_str(&mut value, "repositoryURL"),
+            requirement: take_with(
+                &mut value,
+                "requirement",
+                |requirement| XCVersionRequirement::try_from(requirement).ok(),
+                |requirement| requirement.clone().into(),
+            ),
+            extra: value,
+        })
+    }
+}
+
+impl From<XCRemoteSwiftPackageReferenceData> for PBXHashMap {
+    fn from(value: XCRemoteSwiftPackageReferenceData) -> Self {
+        let fields = [
+            ("repositoryURL", value.repository_url.map(Into::into)),
+            ("requirement", value.requirement.map(Into::into)),
+        ];
+
+        object_map(
+            PBXObjectKind::XCRemoteSwiftPackageReference,
+            fields,
+            value.extra,
+        )
+    }
+}
+
+/// Owned [`XCSwiftPackageProductDependency`]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct XCSwiftPackageProductDependencyData {
+    /// Product name.
+    pub product_name: String,
+    /// Package reference id.
+    pub package: Option<String>,
+    /// Keys that aren't modeled
+    pub extra: PBXHashMap,
+}
+
+impl XCSwiftPackageProductDependencyData {
+    /// Create new dependency on a product of a given package reference id
+    pub fn new<S: Into<String>>(product_name: S, package: S) -> Self {
+        Self {
+            product_name: product_name.into(),
+            package: Some(package.into()),
+            extra: Default::default(),
+        }
+    }
+}
+
+impl TryFrom<PBXHashMap> for XCSwiftPackageProductDependencyData {
+    type Error = anyhow::Error;
+
+    fn try_from(mut value: PBXHashMap) -> Result<Self> {
+        let kind = value.try_remove_kind("isa")?;
+        if kind != PBXObjectKind::XCSwiftPackageProductDependency {
+            anyhow::bail!("expected XCSwiftPackageProductDependency, got {kind}");
+        }
+
+        Ok(Self {
+            product_name: try_take_str(&mut value, "productName")?,
+            package: take_str(&mut value, "package"),
+            extra: value,
+        })
+    }
+}
+
+impl From<XCSwiftPackageProductDependencyData> for PBXHashMap {
+    fn from(value: XCSwiftPackageProductDependencyData) -> Self {
+        let fields = [
+            ("productName", Some(value.product_name.into())),
+            ("package", value.package.map(Into::into)),
+        ];
+
+        object_map(
+            PBXObjectKind::XCSwiftPackageProductDependency,
+            fields,
+            value.extra,
+        )
+    }
+}
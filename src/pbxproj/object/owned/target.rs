@@ -0,0 +1,257 @@
+use super::*;
+
+/// Owned [`PBXTarget`]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PBXTargetData {
+    /// Target Kind
+    pub kind: PBXTargetKind,
+    /// Target name.
+    pub name: String,
+    /// Target product name.
+    pub product_name: Option<String>,
+    /// Target product type.
+    pub product_type: PBXProductType,
+    /// Target build configuration list id.
+    pub build_configuration_list: Option<String>,
+    /// Target build phase ids.
+    pub build_phases: Vec<String>,
+    /// Target build rule ids.
+    pub build_rules: Vec<String>,
+    /// Target dependency ids.
+    pub dependencies: Vec<String>,
+    /// Target product file reference id.
+    pub product_reference: Option<String>,
+    /// Swift package product dependency ids.
+    pub package_product_dependencies: Vec<String>,
+    /// Target product install path. (relevant only for `PBXNativeTarget`)
+    pub product_install_path: Option<String>,
+    /// Path to the build tool that is invoked (relevant only for `PBXLegacyTarget`)
+    pub build_tool_path: Option<String>,
+    /// Build arguments to be passed to the build tool. (relevant only for `PBXLegacyTarget`)
+    pub build_arguments_string: Option<String>,
+    /// Whether or not to pass Xcode build settings as environment variables down to the tool
+    /// when invoked (relevant only for `PBXLegacyTarget`)
+    pub pass_build_settings_in_environment: Option<bool>,
+    /// The directory where the build tool will be invoked during a build
+    pub build_working_directory: Option<String>,
+    /// Keys that aren't modeled
+    pub extra: PBXHashMap,
+}
+
+impl PBXTargetData {
+    /// Create new native target with a given name and product type
+    pub fn new<S: Into<String>>(name: S, product_type: PBXProductType) -> Self {
+        let name = name.into();
+        Self {
+            product_name: Some(name.clone()),
+            name,
+            product_type,
+            ..Default::default()
+        }
+    }
+}
+
+impl TryFrom<PBXHashMap> for PBXTargetData {
+    type Error = anyhow::Error;
+
+    fn try_from(mut value: PBXHashMap) -> Result<Self> {
+        let kind = value
+            .try_remove_kind("isa")?
+            .into_pbx_target()
+            .map_err(|k| anyhow::anyhow!("expected target, got {k}"))?;
+
+        Ok(Self {
+            kind,
+            name: try_take_str(&mut value, "name")?,
+            product_name: take_str(&mut value, "productName"),
+            product_type: take_str(&mut value, "productType")
+                .map(PBXProductType::from)
+                .unwrap_or_default(),
+            build_configuration_list: take_str(&mut value, "buildConfigurationList"),
+            build_phases: take_strings(&mut value, "buildPhases")?,
+            build_rules: take_strings(&mut value, "buildRules")?,
+            dependencies: take_strings(&mut value, "dependencies")?,
+            product_reference: take_str(&mut value, "productReference"),
+            package_product_dependencies: take_strings(&mut value, "packageProductDependencies")?,
+            product_install_path: take_str(&mut value, "productInstallPath"),
+            build_tool_path: take_str(&mut value, "buildToolPath"),
+            build_arguments_string: take_str(&mut value, "buildArgumentsString"),
+            pass_build_settings_in_environment: take_flag(
+                &mut value,
+                "passBuildSettingsInEnvironment",
+            ),
+            build_working_directory: take_str(&mut value, "buildWorkingDirectory"),
+            extra: value,
+        })
+    }
+}
+
+impl From<PBXTargetData> for PBXHashMap {
+    fn from(value: PBXTargetData) -> Self {
+        let is_native = value.kind.is_native();
+        let product_type = match value.product_type {
+            PBXProductType::None => None,
+            product_type => Some(product_type.to_string().into()),
+        };
+        let fields = [
+            ("name", Some(value.name.into())),
+            ("productName", value.product_name.map(Into::into)),
+            ("productType", product_type),
+            (
+                "buildConfigurationList",
+                value.build_configuration_list.map(Into::into),
+            ),
+            ("buildPhases", Some(value.build_phases.into())),
+            (
+                "buildRules",
+                (is_native || !value.build_rules.is_empty()).then(|| value.build_rules.into()),
+            ),
+            ("dependencies", Some(value.dependencies.into())),
+            ("productReference", value.product_reference.map(Into::into)),
+            (
+                "packageProductDependencies",
+                (!value.package_product_dependencies.is_empty())
+                    .then(|| value.package_product_dependencies.into()),
+            ),
+            (
+                "productInstallPath",
+                value.product_install_path.map(Into::into),
+            ),
+            ("buildToolPath", value.build_tool_path.map(Into::into)),
+            (
+                "buildArgumentsString",
+                value.build_arguments_string.map(Into::into),
+            ),
+            (
+                "passBuildSettingsInEnvironment",
+                value.pass_build_settings_in_environment.map(flag),
+            ),
+            (
+                "buildWorkingDirectory",
+                value.build_working_directory.map(Into::into),
+            ),
+        ];
+
+        object_map(PBXObjectKind::PBXTarget(value.kind), fields, value.extra)
+    }
+}
+
+/// Owned [`PBXTargetDependency`]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PBXTargetDependencyData {
+    /// Target name.
+    pub name: Option<String>,
+    /// Platform filter attribute.
+    pub platform_filter: Option<String>,
+    /// Target id
+    pub target: Option<String>,
+    /// Target proxy id
+    pub target_proxy: Option<String>,
+    /// Swift package product dependency id.
+    pub product_ref: Option<String>,
+    /// Keys that aren't modeled
+    pub extra: PBXHashMap,
+}
+
+impl TryFrom<PBXHashMap> for PBXTargetDependencyData {
+    type Error = anyhow::Error;
+
+    fn try_from(mut value: PBXHashMap) -> Result<Self> {
+        let kind = value.try_remove_kind("isa")?;
+        if kind != PBXObjectKind::PBXTargetDependency {
+            anyhow::bail!("expected PBXTargetDependency, got {kind}");
+        }
+
+        Ok(Self {
+            name: take_str(&mut value, "name"),
+            platform_filter: take_str(&mut value, "platformFilter"),
+            target: take_str(&mut value, "target"),
+            target_proxy: take_str(&mut value, "targetProxy"),
+            product_ref: take_str(&mut value, "productRef"),
+            extra: value,
+        })
+    }
+}
+
+impl From<PBXTargetDependencyData> for PBXHashMap {
+    fn from(value: PBXTargetDependencyData) -> Self {
+        let fields = [
+            ("name", value.name.map(Into::into)),
+            ("platformFilter", value.platform_filter.map(Into::into)),
+            ("target", value.target.map(Into::into)),
+            ("targetProxy", value.target_proxy.map(Into::into)),
+            ("productRef", value.product_ref.map(Into::into)),
+        ];
+
+        object_map(PBXObjectKind::PBXTargetDependency, fields, value.extra)
+    }
+}
+
+/// Owned [`PBXContainerItemProxy`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PBXContainerItemProxyData {
+    /// Project id, if proxy is for an object located in current .xcodeproj, otherwise
+    /// PBXFileReference id.
+    pub container_portal: String,
+    /// Element proxy type.
+    pub proxy_type: Option<PBXProxyType>,
+    /// ID of the proxied object.
+    pub remote_global_id: Option<String>,
+    /// Element remote info.
+    pub remote_info: Option<String>,
+    /// Keys that aren't modeled
+    pub extra: PBXHashMap,
+}
+
+impl PBXContainerItemProxyData {
+    /// Create new proxy of a target in a given project
+    pub fn new_target<S: Into<String>>(project: S, target_id: S, target_name: S) -> Self {
+        Self {
+            container_portal: project.into(),
+            proxy_type: Some(PBXProxyType::NativeTarget),
+            remote_global_id: Some(target_id.into()),
+            remote_info: Some(target_name.into()),
+            extra: Default::default(),
+        }
+    }
+}
+
+impl TryFrom<PBXHashMap> for PBXContainerItemProxyData {
+    type Error = anyhow::Error;
+
+    fn try_from(mut value: PBXHashMap) -> Result<Self> {
+        let kind = value.try_remove_kind("isa")?;
+        if kind != PBXObjectKind::PBXContainerItemProxy {
+            anyhow::bail!("expected PBXContainerItemProxy, got {kind}");
+        }
+
+        Ok(Self {
+            container_portal: try_take_str(&mut value, "containerPortal")?,
+            proxy_type: take_with(
+                &mut value,
+                "proxyType",
+                |v| PBXProxyType::try_from(v).ok(),
+                |proxy_type| proxy_type.into(),
+            ),
+            remote_global_id: take_str(&mut value, "remoteGlobalIDString"),
+            remote_info: take_str(&mut value, "remoteInfo"),
+            extra: value,
+        })
+    }
+}
+
+impl From<PBXContainerItemProxyData> for PBXHashMap {
+    fn from(value: PBXContainerItemProxyData) -> Self {
+        let fields = [
+            ("containerPortal", Some(value.container_portal.into())),
+            ("proxyType", value.proxy_type.as_ref().map(Into::into)),
+            (
+                "remoteGlobalIDString",
+                value.remote_global_id.map(Into::into),
+            ),
+            ("remoteInfo", value.remote_info.map(Into::into)),
+        ];
+
+        object_map(PBXObjectKind::PBXContainerItemProxy, fields, value.extra)
+    }
+}
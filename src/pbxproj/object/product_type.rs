@@ -1,7 +1,7 @@
 use tap::Pipe;
 
 /// Target Product Type
-#[derive(Debug, Hash, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Hash, PartialEq, Eq)]
 pub enum PBXProductType {
     /// Application
     Application,
@@ -62,15 +62,10 @@ pub enum PBXProductType {
     /// SystemExtension
     SystemExtension,
     /// None Identified
+    #[default]
     None,
 }
 
-impl Default for PBXProductType {
-    fn default() -> Self {
-        Self::None
-    }
-}
-
 impl PBXProductType {
     /// Return file extension for product type
     pub fn file_extension(&self) -> Option<&str> {
@@ -108,6 +103,36 @@ impl PBXProductType {
     }
 }
 
+impl PBXProductType {
+    /// Return product file name for a given product name, i.e. `libName.a` for static library
+    pub fn file_name(&self, product_name: &str) -> String {
+        match (self, self.file_extension()) {
+            (Self::StaticLibrary, Some(extension)) => format!("lib{product_name}.{extension}"),
+            (_, Some(extension)) => format!("{product_name}.{extension}"),
+            (_, Option::None) => product_name.to_string(),
+        }
+    }
+
+    /// Return explicit file type of product file reference
+    pub fn explicit_file_type(&self) -> &str {
+        match self.file_extension() {
+            Some("app") => "wrapper.application",
+            Some("appex") => "wrapper.app-extension",
+            Some("framework") => "wrapper.framework",
+            Some("xcframework") => "wrapper.xcframework",
+            Some("xctest" | "octest" | "bundle") => "wrapper.cfbundle",
+            Some("a") => "archive.ar",
+            Some("dylib") => "compiled.mach-o.dylib",
+            Some("xpc") => "wrapper.xpc-service",
+            Some("metallib") => "archive.metal-library",
+            Some("systemextension") => "wrapper.system-extension",
+            Some("dext") => "wrapper.driver-extension",
+            Some("instrpkg") => "com.apple.instruments.instrdst",
+            _ => "compiled.mach-o.executable",
+        }
+    }
+}
+
 impl std::fmt::Display for PBXProductType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         use PBXProductType::*;
@@ -1,7 +1,9 @@
 mod dependency;
 mod remote;
+mod semver;
 mod version;
 
 pub use dependency::*;
 pub use remote::*;
+pub use semver::*;
 pub use version::*;
@@ -28,8 +28,7 @@ impl<'a> XCRemoteSwiftPackageReference<'a> {
     pub fn name(&self) -> Option<&str> {
         self.repository_url
             .as_ref()
-            .map(|s| s.split("/").last())
-            .flatten()
+            .and_then(|s| s.split('/').next_back())
     }
 
     /// Get a reference to the xcremote swift package reference's version requirement.
@@ -58,8 +57,7 @@ impl<'a> AsPBXObject<'a> for XCRemoteSwiftPackageReference<'a> {
             repository_url: value.get_string("repositoryURL"),
             version_requirement: value
                 .get_value("requirement")
-                .map(|v| v.try_into().ok())
-                .flatten(),
+                .and_then(|v| v.try_into().ok()),
         })
     }
 }
@@ -0,0 +1,223 @@
+use anyhow::{anyhow, bail, Result};
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Semantic version of a swift package, i.e. `1.4.2` or `2.0.0-beta.1+exp.sha`
+///
+/// Like SwiftPM, a leading `v` and missing minor or patch numbers are accepted when parsing
+/// tags. Build metadata is kept but ignored in comparison.
+#[derive(Debug, Default, Clone)]
+pub struct XCSemanticVersion {
+    /// Major version
+    pub major: u64,
+    /// Minor version
+    pub minor: u64,
+    /// Patch version
+    pub patch: u64,
+    /// Pre-release identifiers, i.e. `["beta", "1"]`
+    pub pre_release: Vec<String>,
+    /// Build metadata identifiers
+    pub build_metadata: Vec<String>,
+}
+
+impl XCSemanticVersion {
+    /// Create new release version
+    pub fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            ..Default::default()
+        }
+    }
+
+    /// Whether version has pre-release identifiers
+    pub fn is_pre_release(&self) -> bool {
+        !self.pre_release.is_empty()
+    }
+
+    /// Next major version, i.e. `2.0.0` for `1.4.2`
+    pub fn next_major(&self) -> Self {
+        Self::new(self.major + 1, 0, 0)
+    }
+
+    /// Next minor version, i.e. `1.5.0` for `1.4.2`
+    pub fn next_minor(&self) -> Self {
+        Self::new(self.major, self.minor + 1, 0)
+    }
+
+    /// Whether major, minor and patch equal ignoring pre-release and build metadata
+    pub(crate) fn is_same_release(&self, other: &Self) -> bool {
+        (self.major, self.minor, self.patch) == (other.major, other.minor, other.patch)
+    }
+}
+
+impl FromStr for XCSemanticVersion {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let version = value.strip_prefix('v').unwrap_or(value);
+        let (version, build_metadata) = match version.split_once('+') {
+            Some((version, build)) => (version, identifiers(build, value)?),
+            None => (version, vec![]),
+        };
+        let (version, pre_release) = match version.split_once('-') {
+            Some((version, pre_release)) => (version, identifiers(pre_release, value)?),
+            None => (version, vec![]),
+        };
+
+        let numbers = version
+            .split('.')
+            .map(|number| {
+                if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+                    bail!("Invalid version {value:?}")
+                }
+                number
+                    .parse::<u64>()
+                    .map_err(|_| anyhow!("Invalid version {value:?}"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if numbers.len() > 3 {
+            bail!("Invalid version {value:?}")
+        }
+
+        Ok(Self {
+            major: numbers[0],
+            minor: numbers.get(1).copied().unwrap_or_default(),
+            patch: numbers.get(2).copied().unwrap_or_default(),
+            pre_release,
+            build_metadata,
+        })
+    }
+}
+
+/// Split dot separated pre-release or build metadata identifiers
+fn identifiers(value: &str, version: &str) -> Result<Vec<String>> {
+    value
+        .split('.')
+        .map(|identifier| {
+            if identifier.is_empty()
+                || !identifier
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+            {
+                bail!("Invalid version {version:?}")
+            }
+            Ok(identifier.to_string())
+        })
+        .collect()
+}
+
+impl Display for XCSemanticVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if self.is_pre_release() {
+            write!(f, "-{}", self.pre_release.join("."))?;
+        }
+        if !self.build_metadata.is_empty() {
+            write!(f, "+{}", self.build_metadata.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+impl Ord for XCSemanticVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.is_pre_release(), other.is_pre_release()) {
+                (false, false) => Ordering::Equal,
+                (false, true) => Ordering::Greater,
+                (true, false) => Ordering::Less,
+                (true, true) => self
+                    .pre_release
+                    .iter()
+                    .zip(other.pre_release.iter())
+                    .map(|(a, b)| compare_identifiers(a, b))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or_else(|| self.pre_release.len().cmp(&other.pre_release.len())),
+            })
+    }
+}
+
+/// Compare pre-release identifiers, numeric ones numerically and lower than alphanumeric ones
+fn compare_identifiers(a: &str, b: &str) -> Ordering {
+    match (a.parse::<u64>(), b.parse::<u64>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+impl PartialOrd for XCSemanticVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for XCSemanticVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for XCSemanticVersion {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(value: &str) -> XCSemanticVersion {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let v = version("v1.4.2-beta.1+exp.sha");
+        assert_eq!((v.major, v.minor, v.patch), (1, 4, 2));
+        assert_eq!(v.pre_release, vec!["beta", "1"]);
+        assert_eq!(v.build_metadata, vec!["exp", "sha"]);
+        assert_eq!(v.to_string(), "1.4.2-beta.1+exp.sha");
+        assert_eq!(version("1.4"), XCSemanticVersion::new(1, 4, 0));
+
+        for invalid in [
+            "",
+            "1.2.3.4",
+            "1..2",
+            "one",
+            "1.2.3-",
+            "1.2.3-beta..1",
+            "1.-2",
+        ] {
+            assert!(invalid.parse::<XCSemanticVersion>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn precedence() {
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1",
+            "1.10.0",
+            "2.0.0",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(
+                version(pair[0]) < version(pair[1]),
+                "{} < {}",
+                pair[0],
+                pair[1]
+            );
+        }
+        assert_eq!(version("1.0.0+build"), version("1.0.0"));
+    }
+}
@@ -1,6 +1,7 @@
-use crate::pbxproj::{PBXHashMap, PBXValue};
-use anyhow::bail;
-use std::collections::HashMap;
+use crate::pbxproj::{PBXHashMap, PBXNumber, PBXValue, XCSemanticVersion};
+use anyhow::{bail, Result};
+use indexmap::IndexMap;
+use std::ops::{Bound, RangeBounds};
 use tap::Pipe;
 
 /// [`XCRemoteSwiftPackageReference`] version rules.
@@ -22,6 +23,88 @@ pub enum XCVersionRequirement {
     Revision(String),
 }
 
+impl XCVersionRequirement {
+    /// Get requirement `kind` as written in pbxproj, i.e. `upToNextMajorVersion`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::UpToNextMajorVersion(_) => "upToNextMajorVersion",
+            Self::UpToNextMinorVersion(_) => "upToNextMinorVersion",
+            Self::Range(..) => "versionRange",
+            Self::Exact(_) => "exactVersion",
+            Self::Branch(_) => "branch",
+            Self::Revision(_) => "revision",
+        }
+    }
+
+    /// Get allowed versions as lower and upper bounds, None for branch and revision.
+    ///
+    /// Upper bounds of next major, next minor and range requirements are exclusive, as in Xcode.
+    pub fn bounds(&self) -> Result<Option<(Bound<XCSemanticVersion>, Bound<XCSemanticVersion>)>> {
+        use Bound::*;
+
+        let bounds = match self {
+            Self::UpToNextMajorVersion(min) => {
+                let min = min.parse::<XCSemanticVersion>()?;
+                let max = min.next_major();
+                (Included(min), Excluded(max))
+            }
+            Self::UpToNextMinorVersion(min) => {
+                let min = min.parse::<XCSemanticVersion>()?;
+                let max = min.next_minor();
+                (Included(min), Excluded(max))
+            }
+            Self::Range(min, max) => (Included(min.parse()?), Excluded(max.parse()?)),
+            Self::Exact(version) => {
+                let version = version.parse::<XCSemanticVersion>()?;
+                (Included(version.clone()), Included(version))
+            }
+            Self::Branch(_) | Self::Revision(_) => return Ok(None),
+        };
+        Ok(Some(bounds))
+    }
+
+    /// Whether a given version is allowed by requirement.
+    ///
+    /// Like SwiftPM, pre-release versions only match when a bound is a pre-release too, and
+    /// never match pre-releases of a release upper bound, i.e. `2.0.0-beta` isn't allowed by
+    /// `1.0.0..<2.0.0`. Branch, revision and unparsable requirements match no version.
+    pub fn matches(&self, version: &XCSemanticVersion) -> bool {
+        let (lower, upper) = match self.bounds() {
+            Ok(Some(bounds)) => bounds,
+            _ => return false,
+        };
+
+        if version.is_pre_release() {
+            let is_pre_release = |bound: &Bound<XCSemanticVersion>| match bound {
+                Bound::Included(v) | Bound::Excluded(v) => v.is_pre_release(),
+                Bound::Unbounded => false,
+            };
+            if !is_pre_release(&lower) && !is_pre_release(&upper) {
+                return false;
+            }
+            if let Bound::Excluded(upper) = &upper {
+                if !upper.is_pre_release() && upper.is_same_release(version) {
+                    return false;
+                }
+            }
+        }
+
+        (lower, upper).contains(version)
+    }
+
+    /// Get newest tag allowed by requirement, skipping tags that aren't versions
+    pub fn newest_matching<'t, I>(&self, tags: I) -> Option<&'t str>
+    where
+        I: IntoIterator<Item = &'t str>,
+    {
+        tags.into_iter()
+            .filter_map(|tag| Some((tag.parse::<XCSemanticVersion>().ok()?, tag)))
+            .filter(|(version, _)| self.matches(version))
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, tag)| tag)
+    }
+}
+
 impl TryFrom<&PBXValue> for XCVersionRequirement {
     type Error = anyhow::Error;
 
@@ -29,61 +112,208 @@ impl TryFrom<&PBXValue> for XCVersionRequirement {
         let map = value
             .as_object()
             .ok_or_else(|| anyhow::anyhow!("Can get XCVersionRequirement for non object type"))?;
-        let key = map.try_get_string("kind")?;
-        match key.as_str() {
-            "branсh" => Self::Branch(map.try_get_string(&key)?.to_string()),
-            "revision" => Self::Revision(map.try_get_string(&key)?.to_string()),
-            "exactVersion" => Self::Exact(map.try_get_string("version")?.to_string()),
-            "versionRange" => {
-                let min = map.try_get_string("minimumVersion")?;
-                let max = map.try_get_string("maximumVersion")?;
-                Self::Range(min.to_string(), max.to_string())
-            }
-            "upToNextMinorVersion" => {
-                let min = map.try_get_string("minimumVersion")?;
-                Self::UpToNextMinorVersion(min.to_string())
-            }
-            "upToNextMajorVersion" => {
-                let min = map.try_get_string("minimumVersion")?;
-                Self::UpToNextMajorVersion(min.to_string())
-            }
-            k => bail!("Unkown kind {k}"),
+        // Values may be read as numbers, i.e. `version = 1.4.2` or an all digits revision
+        let get = |key: &str| map.try_get_str(key).map(str::to_string);
+
+        match map.try_get_str("kind")? {
+            "upToNextMajorVersion" => Self::UpToNextMajorVersion(get("minimumVersion")?),
+            "upToNextMinorVersion" => Self::UpToNextMinorVersion(get("minimumVersion")?),
+            "versionRange" => Self::Range(get("minimumVersion")?, get("maximumVersion")?),
+            "exactVersion" => Self::Exact(get("version")?),
+            "branch" => Self::Branch(get("branch")?),
+            "revision" => Self::Revision(get("revision")?),
+            kind => bail!("Unknown kind {kind}"),
         }
         .pipe(Ok)
     }
 }
 
+/// Value as the parser reads it, i.e. unquoted `1.4.2` or an all digits revision is a number
+fn scalar_value(value: String) -> PBXValue {
+    match PBXNumber::new(value.as_str()) {
+        Some(number) => PBXValue::Number(number),
+        None => value.into(),
+    }
+}
+
 impl From<XCVersionRequirement> for PBXValue {
     fn from(value: XCVersionRequirement) -> Self {
-        let mut collect = HashMap::default();
-        match value {
-            XCVersionRequirement::UpToNextMajorVersion(v) => {
-                collect.insert("kind".to_string(), "upToNextMajorVersion".into());
-                collect.insert("minimumVersion".to_string(), v.into());
-            }
-            XCVersionRequirement::UpToNextMinorVersion(v) => {
-                collect.insert("kind".to_string(), "upToNextMinorVersion".into());
-                collect.insert("minimumVersion".to_string(), v.into());
-            }
-            XCVersionRequirement::Range(s, e) => {
-                collect.insert("kind".to_string(), "versionRange".into());
-                collect.insert("minimumVersion".to_string(), e.into());
-                collect.insert("maximumVersion".to_string(), s.into());
-            }
-            XCVersionRequirement::Exact(v) => {
-                collect.insert("kind".to_string(), "exactVersion".into());
-                collect.insert("version".to_string(), v.into());
-            }
-            XCVersionRequirement::Branch(v) => {
-                collect.insert("kind".to_string(), "branch".into());
-                collect.insert("branch".to_string(), v.into());
-            }
-            XCVersionRequirement::Revision(v) => {
-                collect.insert("kind".to_string(), "revision".into());
-                collect.insert("revision".to_string(), v.into());
+        let kind = value.kind();
+        let fields = match value {
+            XCVersionRequirement::UpToNextMajorVersion(min)
+            | XCVersionRequirement::UpToNextMinorVersion(min) => {
+                vec![("minimumVersion", scalar_value(min))]
             }
+            XCVersionRequirement::Range(min, max) => vec![
+                ("minimumVersion", scalar_value(min)),
+                ("maximumVersion", scalar_value(max)),
+            ],
+            XCVersionRequirement::Exact(version) => vec![("version", scalar_value(version))],
+            XCVersionRequirement::Branch(branch) => vec![("branch", scalar_value(branch))],
+            XCVersionRequirement::Revision(revision) => vec![("revision", scalar_value(revision))],
+        };
+
+        // Keys are sorted as Xcode writes them
+        let mut map = std::iter::once(("kind", kind.into()))
+            .chain(fields)
+            .map(|(key, value)| (key.to_string(), value))
+            .collect::<IndexMap<_, _>>();
+        map.sort_keys();
+
+        PBXValue::Object(PBXHashMap::new(map))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbxproj::escape_string;
+    use proptest::prelude::*;
+    use XCVersionRequirement::*;
+
+    fn matches(requirement: &XCVersionRequirement, version: &str) -> bool {
+        requirement.matches(&version.parse().unwrap())
+    }
+
+    #[test]
+    fn matches_versions() {
+        let major = UpToNextMajorVersion("1.2.0".into());
+        assert!(matches(&major, "1.2.0"));
+        assert!(matches(&major, "1.9.9"));
+        assert!(!matches(&major, "1.1.9"));
+        assert!(!matches(&major, "2.0.0"));
+        assert!(!matches(&major, "2.0.0-beta"));
+        assert!(!matches(&major, "1.3.0-beta"));
+
+        let minor = UpToNextMinorVersion("1.2.3".into());
+        assert!(matches(&minor, "1.2.9"));
+        assert!(!matches(&minor, "1.3.0"));
+
+        let range = Range("1.0.0".into(), "1.5.0".into());
+        assert!(matches(&range, "1.0.0"));
+        assert!(matches(&range, "1.4.9"));
+        assert!(!matches(&range, "1.5.0"));
+
+        let exact = Exact("1.4.2".into());
+        assert!(matches(&exact, "1.4.2"));
+        assert!(matches(&exact, "v1.4.2"));
+        assert!(!matches(&exact, "1.4.3"));
+
+        let beta = UpToNextMajorVersion("2.0.0-beta.1".into());
+        assert!(matches(&beta, "2.0.0-beta.2"));
+        assert!(matches(&beta, "2.1.0"));
+        assert!(!matches(&beta, "2.0.0-alpha"));
+
+        assert!(!matches(&Branch("main".into()), "1.0.0"));
+        assert!(!matches(&Exact("latest".into()), "1.0.0"));
+    }
+
+    #[test]
+    fn bounds() {
+        let (lower, upper) = UpToNextMinorVersion("1.2.3".into())
+            .bounds()
+            .unwrap()
+            .unwrap();
+        assert_eq!(lower, Bound::Included(XCSemanticVersion::new(1, 2, 3)));
+        assert_eq!(upper, Bound::Excluded(XCSemanticVersion::new(1, 3, 0)));
+        assert!(Revision("abc".into()).bounds().unwrap().is_none());
+        assert!(Exact("one".into()).bounds().is_err());
+    }
+
+    #[test]
+    fn newest_matching() {
+        let tags = ["1.3.0", "v1.4.0", "1.5.0-beta", "2.0.0", "nightly"];
+        let requirement = UpToNextMajorVersion("1.2.0".into());
+        assert_eq!(requirement.newest_matching(tags), Some("v1.4.0"));
+        assert_eq!(
+            Range("1.0.0".into(), "1.4.0".into()).newest_matching(tags),
+            Some("1.3.0")
+        );
+        assert_eq!(Exact("3.0.0".into()).newest_matching(tags), None);
+    }
+
+    #[test]
+    fn from_pbxproj() {
+        let value = |fields: &[(&str, &str)]| {
+            PBXValue::Object(
+                fields
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), crate::pbxproj::parser::unquoted_value(v)))
+                    .collect(),
+            )
+        };
+        let cases = [
+            (
+                value(&[
+                    ("kind", "versionRange"),
+                    ("maximumVersion", "2.0.0"),
+                    ("minimumVersion", "1.2.0"),
+                ]),
+                Range("1.2.0".into(), "2.0.0".into()),
+            ),
+            (
+                value(&[("branch", "main"), ("kind", "branch")]),
+                Branch("main".into()),
+            ),
+            (
+                value(&[("kind", "revision"), ("revision", "1234567")]),
+                Revision("1234567".into()),
+            ),
+        ];
+        for (value, requirement) in cases {
+            assert_eq!(XCVersionRequirement::try_from(&value).unwrap(), requirement);
+            assert_eq!(PBXValue::from(requirement), value);
         }
+    }
 
-        PBXValue::Object(PBXHashMap::new(collect))
+    /// Render requirement as pbxproj text, the way Xcode writes it
+    fn to_text(value: &PBXValue) -> String {
+        match value {
+            PBXValue::Object(map) => map
+                .iter()
+                .map(|(k, v)| format!("{} = {}; ", escape_string(k), to_text(v)))
+                .collect::<String>()
+                .pipe(|fields| format!("{{ {fields}}}")),
+            value => escape_string(value.as_str().unwrap()).into_owned(),
+        }
+    }
+
+    fn requirement() -> impl Strategy<Value = XCVersionRequirement> {
+        let version =
+            r"(0|[1-9][0-9]{0,2})\.[0-9]{1,3}\.[0-9]{1,3}(-(alpha|beta|rc)(\.[0-9]{1,2})?)?";
+        prop_oneof![
+            version.prop_map(UpToNextMajorVersion),
+            version.prop_map(UpToNextMinorVersion),
+            (version, version).prop_map(|(min, max)| Range(min, max)),
+            version.prop_map(Exact),
+            r"[a-z][a-z0-9._/-]{0,24}".prop_map(Branch),
+            r"[0-9a-f]{7,40}".prop_map(Revision),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip_value(requirement in requirement()) {
+            let value = PBXValue::from(requirement.clone());
+            prop_assert_eq!(value.as_object().unwrap().get_str("kind"), Some(requirement.kind()));
+            prop_assert_eq!(XCVersionRequirement::try_from(&value).unwrap(), requirement);
+        }
+
+        #[test]
+        fn round_trip_text(requirement in requirement()) {
+            let text = format!(
+                "// !$*UTF8*$!\n{{ requirement = {}; }}\n",
+                to_text(&requirement.clone().into())
+            );
+            let parsed = [
+                crate::pbxproj::parser::PBXParser::try_from_str(&text).unwrap(),
+                crate::pbxproj::pest::PBXProjectParser::try_from_str(&text).unwrap(),
+            ];
+            for map in parsed {
+                let value = map.get_value("requirement").unwrap();
+                prop_assert_eq!(value, &PBXValue::from(requirement.clone()));
+                prop_assert_eq!(XCVersionRequirement::try_from(value).unwrap(), requirement.clone());
+            }
+        }
     }
 }
@@ -1,9 +1,13 @@
 mod dependency;
+mod headers;
 mod info;
 mod platform;
 pub use dependency::*;
+pub use headers::*;
 
 use anyhow::Result;
+use std::collections::HashMap;
+use std::path::Path;
 
 use crate::pbxproj::*;
 
@@ -54,6 +58,137 @@ impl<'a> PBXTarget<'a> {
     pub fn info(&'a self, objects: &'a PBXObjectCollection) -> PBXTargetInfo {
         PBXTargetInfo::new(self, objects)
     }
+
+    /// Get resolved build settings for a given configuration name, including settings of base
+    /// `.xcconfig` files.
+    ///
+    /// `source_root` is the directory containing the `.xcodeproj`.
+    pub fn build_settings<P: AsRef<Path>>(
+        &self,
+        objects: &PBXObjectCollection,
+        configuration: &str,
+        source_root: P,
+    ) -> Result<XCBuildSettings> {
+        self.layered_build_settings(objects, configuration, source_root.as_ref(), None)
+    }
+
+    /// Get resolved build settings like [`PBXTarget::build_settings`], including build
+    /// directory settings, i.e. `BUILT_PRODUCTS_DIR` or `DERIVED_FILE_DIR`, derived from given
+    /// locations, and product paths, i.e. `INFOPLIST_PATH`, derived from the product type.
+    /// Configurations can still override them, i.e. with `SYMROOT` or `WRAPPER_EXTENSION`.
+    pub fn build_settings_with_locations<P: AsRef<Path>>(
+        &self,
+        objects: &PBXObjectCollection,
+        configuration: &str,
+        source_root: P,
+        locations: &XCBuildLocations,
+    ) -> Result<XCBuildSettings> {
+        self.layered_build_settings(
+            objects,
+            configuration,
+            source_root.as_ref(),
+            Some(locations),
+        )
+    }
+
+    fn layered_build_settings(
+        &self,
+        objects: &PBXObjectCollection,
+        configuration: &str,
+        source_root: &Path,
+        locations: Option<&XCBuildLocations>,
+    ) -> Result<XCBuildSettings> {
+        let target_configuration = self
+            .build_configuration_list
+            .as_ref()
+            .and_then(|list| list.get_configuration_by_name(configuration))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No {configuration:?} configuration found for {:?}",
+                    self.name
+                )
+            })?;
+
+        let source_root_str = source_root.to_string_lossy();
+        let target_name = self.name.map(String::as_str).unwrap_or_default();
+        let mut settings = XCBuildSettings::default();
+
+        settings.insert("SRCROOT", source_root_str.as_ref());
+        settings.insert("SOURCE_ROOT", source_root_str.as_ref());
+        settings.insert("PROJECT_DIR", source_root_str.as_ref());
+        settings.insert("CONFIGURATION", configuration);
+        settings.insert("TARGET_NAME", target_name);
+        settings.insert(
+            "PRODUCT_NAME",
+            self.product_name.map(String::as_str).unwrap_or(target_name),
+        );
+        for (key, value) in locations
+            .map(XCBuildLocations::settings)
+            .unwrap_or_default()
+        {
+            settings.insert(key, value);
+        }
+
+        if let Some(project) = objects
+            .projects()
+            .into_iter()
+            .find(|p| p.targets.iter().any(|t| t.id == self.id))
+        {
+            if let Some(project_configuration) = project
+                .build_configuration_list
+                .get_configuration_by_name(configuration)
+            {
+                settings.layer_configuration(project_configuration, objects, source_root)?;
+            }
+        }
+
+        settings.layer_configuration(target_configuration, objects, source_root)?;
+
+        if locations.is_some() {
+            if !settings.contains_key("EFFECTIVE_PLATFORM_NAME") {
+                let sdkroot = settings.expand("$(SDKROOT)");
+                settings.insert("EFFECTIVE_PLATFORM_NAME", effective_platform_name(&sdkroot));
+            }
+            // macOS bundles, which have no platform suffix, are deep
+            let is_deep = settings.expand("$(EFFECTIVE_PLATFORM_NAME)").is_empty();
+            for (key, value) in product_settings(&self.product_type, is_deep) {
+                if !settings.contains_key(key) {
+                    settings.insert(key, value);
+                }
+            }
+        }
+
+        Ok(settings.expanded())
+    }
+
+    /// Get products of other targets copied by copy files build phases, resolved to the
+    /// targets producing them.
+    pub fn embedded_products(
+        &self,
+        objects: &'a PBXObjectCollection,
+    ) -> Vec<PBXEmbeddedProduct<'a>> {
+        let producers = objects
+            .get_ids_by_kind(|kind| kind.is_pbx_target())
+            .into_iter()
+            .filter_map(|id| Some((objects.get_object(id)?.get_str("productReference")?, id)))
+            .collect::<HashMap<_, _>>();
+
+        self.build_phases
+            .iter()
+            .filter(|phase| phase.is_copy_files())
+            .flat_map(|phase| {
+                phase.files.iter().filter_map(|file| {
+                    let file = file.file.as_ref()?;
+                    let target = producers.get(file.id.as_str())?;
+                    Some(PBXEmbeddedProduct {
+                        destination: phase.destination?,
+                        subpath: phase.destination_subpath,
+                        target: objects.get_target(target)?,
+                    })
+                })
+            })
+            .collect()
+    }
 }
 
 impl<'a> AsPBXObject<'a> for PBXTarget<'a> {
@@ -105,9 +240,7 @@ impl<'a> AsPBXObject<'a> for PBXTarget<'a> {
             kind,
             build_tool_path: value.get_string("buildToolPath"),
             build_arguments_string: value.get_string("buildArgumentsString"),
-            pass_build_settings_in_environment: value
-                .get_number("passBuildSettingsInEnvironment")
-                .map(|n| n == &1),
+            pass_build_settings_in_environment: value.get_flag("passBuildSettingsInEnvironment"),
             build_working_directory: value.get_string("buildWorkingDirectory"),
         })
     }
@@ -132,4 +265,41 @@ mod tests {
     }
 
     test_samples![demo1, demo2, demo3, demo4, demo5, demo6, demo7, demo8, demo9, demo10, demo11];
+
+    #[test]
+    fn embedded_products() {
+        use crate::pbxproj::*;
+
+        let mut project = test_demo_file!(demo2);
+        let target = project
+            .get_target_by_name("backbase-showcase-mobile-ios")
+            .unwrap();
+        // Only the BackbaseCXP.framework file reference is copied
+        assert!(target.embedded_products(&project).is_empty());
+
+        let build_file = project.objects.push(PBXBuildFileData {
+            file_ref: Some("8EF0E26C1B340CF900CF1FCC".into()),
+            ..Default::default()
+        });
+        project.objects.update("D1F293BB1C905989005ECD85", |phase| {
+            phase
+                .get_value_mut("files")
+                .and_then(PBXValue::as_vec_mut)
+                .unwrap()
+                .0
+                .push(build_file.into())
+        });
+
+        let target = project
+            .get_target_by_name("backbase-showcase-mobile-ios")
+            .unwrap();
+        let products = target.embedded_products(&project);
+        assert_eq!(products.len(), 1);
+        assert_eq!(products[0].destination, PBXCopyFilesDestination::Products);
+        assert_eq!(
+            products[0].subpath.map(String::as_str),
+            Some("$(CONTENTS_FOLDER_PATH)/Watch")
+        );
+        assert_eq!(products[0].target.id, "8EF0E26B1B340CF900CF1FCC");
+    }
 }
@@ -0,0 +1,171 @@
+use anyhow::Result;
+use derive_is_enum_variant::is_enum_variant;
+use std::path::{Path, PathBuf};
+
+use crate::pbxproj::*;
+
+/// Visibility of a header in a framework
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, is_enum_variant)]
+pub enum PBXHeaderVisibility {
+    /// Copied to `Headers` of the framework
+    Public,
+    /// Copied to `PrivateHeaders` of the framework
+    Private,
+    /// Not copied, only visible to the target itself
+    Project,
+}
+
+impl From<&PBXBuildFile<'_>> for PBXHeaderVisibility {
+    fn from(file: &PBXBuildFile<'_>) -> Self {
+        let attributes = file.attributes();
+        if attributes.contains(&PBXBuildFileAttribute::Public) {
+            Self::Public
+        } else if attributes.contains(&PBXBuildFileAttribute::Private) {
+            Self::Private
+        } else {
+            Self::Project
+        }
+    }
+}
+
+/// Headers of a [`PBXTarget`] classified by visibility, with absolute paths
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PBXTargetHeaders {
+    /// Public headers
+    pub public: Vec<PathBuf>,
+    /// Private headers
+    pub private: Vec<PathBuf>,
+    /// Project headers
+    pub project: Vec<PathBuf>,
+}
+
+impl PBXTargetHeaders {
+    /// Get headers with a given visibility
+    pub fn get(&self, visibility: PBXHeaderVisibility) -> &Vec<PathBuf> {
+        match visibility {
+            PBXHeaderVisibility::Public => &self.public,
+            PBXHeaderVisibility::Private => &self.private,
+            PBXHeaderVisibility::Project => &self.project,
+        }
+    }
+
+    /// Generate umbrella header importing every public header of a given module.
+    ///
+    /// The umbrella header itself, `<module_name>.h`, is skipped. Version symbols are named after
+    /// `module_name` as a C identifier, i.e. `My_KitVersionNumber` for `My-Kit`.
+    pub fn umbrella_header(&self, module_name: &str) -> String {
+        let umbrella = format!("{module_name}.h");
+        let identifier = c99_identifier(module_name);
+        let mut headers = self
+            .public
+            .iter()
+            .filter_map(|path| path.file_name()?.to_str())
+            .filter(|name| name != &umbrella)
+            .collect::<Vec<_>>();
+        headers.sort_unstable();
+        headers.dedup();
+
+        let mut content = format!(
+            "#import <Foundation/Foundation.h>\n\n\
+            //! Project version number for {module_name}.\n\
+            FOUNDATION_EXPORT double {identifier}VersionNumber;\n\n\
+            //! Project version string for {module_name}.\n\
+            FOUNDATION_EXPORT const unsigned char {identifier}VersionString[];\n\n"
+        );
+        for header in headers {
+            content.push_str(&format!("#import <{module_name}/{header}>\n"));
+        }
+        content
+    }
+
+    /// Generate `module.modulemap` of a framework module using its umbrella header
+    pub fn module_map(module_name: &str) -> String {
+        format!(
+            "framework module {module_name} {{\n  \
+            umbrella header \"{module_name}.h\"\n\n  \
+            export *\n  \
+            module * {{ export * }}\n\
+            }}\n"
+        )
+    }
+}
+
+impl<'a> PBXTarget<'a> {
+    /// Get headers of `Headers` build phases classified by build file attributes.
+    ///
+    /// `source_root` is the directory containing the `.xcodeproj`.
+    pub fn headers<P: AsRef<Path>>(
+        &self,
+        objects: &PBXObjectCollection,
+        source_root: P,
+    ) -> Result<PBXTargetHeaders> {
+        let mut headers = PBXTargetHeaders::default();
+        let files = self
+            .build_phases
+            .iter()
+            .filter(|phase| phase.is_headers())
+            .flat_map(|phase| phase.files.iter());
+
+        for file in files {
+            let Some(reference) = file.file.as_ref() else {
+                continue;
+            };
+            let path = reference.full_path(objects, source_root.as_ref())?;
+            let list = match PBXHeaderVisibility::from(file) {
+                PBXHeaderVisibility::Public => &mut headers.public,
+                PBXHeaderVisibility::Private => &mut headers.private,
+                PBXHeaderVisibility::Project => &mut headers.project,
+            };
+            if !list.contains(&path) {
+                list.push(path);
+            }
+        }
+
+        Ok(headers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbxproj::test_demo_file;
+
+    #[test]
+    fn target_headers() {
+        let project = test_demo_file!(demo4);
+        let target = project.get_target_by_name("BackbaseCXP").unwrap();
+        let headers = target.headers(&project, "/project").unwrap();
+
+        assert_eq!(headers.public.len(), 38);
+        assert!(headers.private.is_empty());
+        assert_eq!(headers.project.len(), 89);
+        assert!(headers.public.iter().all(|path| path.is_absolute()));
+
+        let umbrella = headers.umbrella_header("BackbaseCXP");
+        assert!(umbrella.contains("#import <BackbaseCXP/CXPPortalConfiguration.h>\n"));
+        assert!(!umbrella.contains("<BackbaseCXP/BackbaseCXP.h>"));
+        assert_eq!(
+            umbrella.matches("#import <BackbaseCXP/").count(),
+            headers.public.len() - 1
+        );
+        assert!(PBXTargetHeaders::module_map("BackbaseCXP")
+            .contains("umbrella header \"BackbaseCXP.h\""));
+    }
+
+    #[test]
+    fn umbrella_header_identifiers() {
+        let headers = PBXTargetHeaders {
+            public: vec!["/project/My-Kit.h".into(), "/project/Kit.h".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            headers.umbrella_header("My-Kit"),
+            "#import <Foundation/Foundation.h>\n\n\
+            //! Project version number for My-Kit.\n\
+            FOUNDATION_EXPORT double My_KitVersionNumber;\n\n\
+            //! Project version string for My-Kit.\n\
+            FOUNDATION_EXPORT const unsigned char My_KitVersionString[];\n\n\
+            #import <My-Kit/Kit.h>\n"
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use crate::pbxproj::{PBXObjectCollection, XCConfigurationList};
+
+use super::{PBXTarget, PBXTargetPlatform};
+
+/// Summary of target infomration
+#[derive(Debug)]
+pub struct PBXTargetInfo {
+    /// Target's platform
+    pub platform: PBXTargetPlatform,
+    /// Target's configurations
+    pub configurations: Vec<String>,
+}
+
+impl PBXTargetInfo {
+    /// Create new target info object
+    pub fn new<'a>(target: &'a PBXTarget, objects: &'a PBXObjectCollection) -> Self {
+        let mut configurations = vec![];
+
+        if let Some(ref bclist) = target.build_configuration_list {
+            configurations.extend(
+                bclist
+                    .build_configurations
+                    .iter()
+                    .map(|c| c.name.to_string()),
+            );
+        }
+
+        Self {
+            platform: Self::platform(target.build_configuration_list.as_ref(), objects),
+            configurations,
+        }
+    }
+
+    /// Get platform of a target with a given build configuration list
+    pub(crate) fn platform(
+        bclist: Option<&XCConfigurationList>,
+        objects: &PBXObjectCollection,
+    ) -> PBXTargetPlatform {
+        let Some(bclist) = bclist else {
+            return PBXTargetPlatform::default();
+        };
+
+        if let Some(sdkroot) = bclist.extract_sdkroot_from_children(objects) {
+            return PBXTargetPlatform::from_sdk_root(sdkroot.as_str());
+        }
+
+        // NOTE: raw project objects are used, since resolving PBXProject resolves its targets,
+        // which never terminates for cyclic target dependencies.
+        tracing::trace!("Find SDKROOT: Trying PBXProject Objects");
+        let mut sdkroots = objects
+            .iter()
+            .filter(|(_, v)| {
+                v.get_kind("isa")
+                    .map(|k| k.is_pbx_project())
+                    .unwrap_or_default()
+            })
+            .flat_map(|(_, p)| p.get_string("buildConfigurationList"))
+            .flat_map(|id| objects.get::<XCConfigurationList, _>(id))
+            .flat_map(|list| list.extract_sdkroot_from_children(objects))
+            .collect::<Vec<_>>();
+
+        sdkroots.dedup();
+
+        let Some(sdkroot) = sdkroots.first() else {
+            tracing::trace!(
+                "Find SDKROOT: using target info nor PBXPRoject data {:?}",
+                bclist.id
+            );
+            return PBXTargetPlatform::default();
+        };
+        if sdkroots.len() > 1 {
+            tracing::trace!("Find SDKROOT: Get more then one sdkroot  {:?}", bclist.id);
+            tracing::trace!("Find SDKROOT Using {:?} as sdkroot", sdkroot);
+        }
+        PBXTargetPlatform::from_sdk_root(sdkroot.as_str())
+    }
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use tap::Pipe;
+
+use derive_is_enum_variant::is_enum_variant;
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Hash, PartialEq, Eq, is_enum_variant)]
+/// Target Platform based
+pub enum PBXTargetPlatform {
+    /// iOS Platform
+    #[serde(rename = "iOS")]
+    IOS,
+    /// watchOS Platform
+    #[serde(rename = "watchOS")]
+    WatchOS,
+    /// tvOs Platform
+    #[serde(rename = "tvOS")]
+    TvOS,
+    /// macOS Platform
+    #[serde(rename = "macOS")]
+    MacOS,
+    /// visionOS Platform
+    #[serde(rename = "xrOS")]
+    XrOS,
+    /// Unknown or not support platform
+    #[default]
+    Unknown,
+}
+
+impl PBXTargetPlatform {
+    /// Get Target Platfrom from sdkroot
+    pub fn from_sdk_root(sdk_root: &str) -> Self {
+        match sdk_root {
+            "iphoneos" => Self::IOS,
+            "macosx" => Self::MacOS,
+            "appletvos" => Self::TvOS,
+            "watchos" => Self::WatchOS,
+            "xros" => Self::XrOS,
+            _ => Self::Unknown,
+        }
+    }
+    /// Get sdkroot of platform
+    pub fn sdk_root(&self) -> Option<&str> {
+        match self {
+            Self::IOS => "iphoneos",
+            Self::MacOS => "macosx",
+            Self::TvOS => "appletvos",
+            Self::WatchOS => "watchos",
+            Self::XrOS => "xros",
+            Self::Unknown => return None,
+        }
+        .pipe(Some)
+    }
+
+    /// Get build setting name of platform deployment target, i.e. `IPHONEOS_DEPLOYMENT_TARGET`
+    pub fn deployment_target_setting(&self) -> Option<&str> {
+        match self {
+            Self::IOS => "IPHONEOS_DEPLOYMENT_TARGET",
+            Self::MacOS => "MACOSX_DEPLOYMENT_TARGET",
+            Self::TvOS => "TVOS_DEPLOYMENT_TARGET",
+            Self::WatchOS => "WATCHOS_DEPLOYMENT_TARGET",
+            Self::XrOS => "XROS_DEPLOYMENT_TARGET",
+            Self::Unknown => return None,
+        }
+        .pipe(Some)
+    }
+
+    /// Get PBXTargetPlatform from simulator identifer
+    pub fn from_identifer(identifer: &str) -> Self {
+        let name = identifer.replace("com.apple.CoreSimulator.SimRuntime.", "");
+        let platform_str = name.split("-").next().unwrap().to_string();
+        match Self::from_str(&platform_str) {
+            Ok(res) => res,
+            Err(e) => {
+                tracing::error!("Platfrom from str: {e}");
+                Self::Unknown
+            }
+        }
+    }
+}
+
+impl FromStr for PBXTargetPlatform {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, String> {
+        match s {
+            "iOS" => Ok(Self::IOS),
+            "watchOS" => Ok(Self::WatchOS),
+            "tvOS" => Ok(Self::TvOS),
+            "macOS" => Ok(Self::MacOS),
+            "xrOS" => Ok(Self::XrOS),
+            _ => Ok(Self::Unknown),
+        }
+    }
+}
+
+impl std::fmt::Display for PBXTargetPlatform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::IOS => "iOS",
+            Self::WatchOS => "watchOS",
+            Self::TvOS => "tvOS",
+            Self::MacOS => "macOS",
+            Self::XrOS => "xrOS",
+            _ => "",
+        };
+        write!(f, "{value}")
+    }
+}
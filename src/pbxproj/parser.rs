@@ -0,0 +1,396 @@
+//! Hand-written pbxproj parser.
+//!
+//! Tokens are borrowed from the input and only copied once stored in [`PBXHashMap`], avoiding
+//! the intermediate allocations of the pest based parser.
+use super::object::PBXObjectKind;
+use super::{unescape_string, PBXHashMap, PBXNumber, PBXValue, PBXVec};
+use anyhow::{anyhow, Result};
+use indexmap::IndexMap;
+use std::borrow::Cow;
+use std::path::Path;
+use tap::Pipe;
+
+const HEADER: &str = "// !$*UTF8*$!";
+
+/// Object kinds parsed as [`PBXValue::Kind`], any other isa is kept as [`PBXValue::String`].
+const KINDS: [&str; 23] = [
+    "PBXBuildFile",
+    "PBXFileReference",
+    "PBXLegacyTarget",
+    "PBXNativeTarget",
+    "PBXAggregateTarget",
+    "PBXProject",
+    "PBXGroup",
+    "PBXHeadersBuildPhase",
+    "PBXFrameworksBuildPhase",
+    "XCConfigurationList",
+    "PBXResourcesBuildPhase",
+    "PBXShellScriptBuildPhase",
+    "PBXSourcesBuildPhase",
+    "PBXTargetDependency",
+    "PBXVariantGroup",
+    "XCBuildConfiguration",
+    "PBXCopyFilesBuildPhase",
+    "PBXContainerItemProxy",
+    "XCVersionGroup",
+    "PBXRezBuildPhase",
+    "PBXBuildRule",
+    "XCRemoteSwiftPackageReference",
+    "XCSwiftPackageProductDependency",
+];
+
+/// Zero-copy pbxproj parser
+pub(crate) struct PBXParser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> PBXParser<'a> {
+    pub fn try_parse_from_file<P>(path: P) -> Result<PBXHashMap>
+    where
+        P: AsRef<Path> + std::fmt::Debug,
+    {
+        std::fs::read_to_string(&path)
+            .map_err(|e| anyhow!("PBXProjectData from path {path:?}: {e}"))?
+            .pipe(Self::try_from_str)
+    }
+
+    pub fn try_from_str<S>(content: S) -> Result<PBXHashMap>
+    where
+        S: AsRef<str>,
+    {
+        let mut parser = PBXParser {
+            input: content.as_ref(),
+            position: 0,
+        };
+        parser.file()
+    }
+
+    fn file(&mut self) -> Result<PBXHashMap> {
+        self.skip_whitespace();
+        if !self.rest().starts_with(HEADER) {
+            return Err(self.error("Expected `// !$*UTF8*$!` header"));
+        }
+        self.position += HEADER.len();
+
+        self.skip_trivia()?;
+        self.expect(b'{')?;
+        let object = self.object()?;
+
+        self.skip_trivia()?;
+        if self.position < self.input.len() {
+            return Err(self.error("Expected end of file"));
+        }
+
+        Ok(object)
+    }
+
+    /// Parse object fields, after the opening `{`
+    fn object(&mut self) -> Result<PBXHashMap> {
+        let mut fields = IndexMap::new();
+
+        loop {
+            self.skip_trivia()?;
+            if self.eat(b'}') {
+                break;
+            }
+
+            let key = self.key()?;
+            self.skip_trivia()?;
+            self.expect(b'=')?;
+            let value = self.value()?;
+            self.skip_trivia()?;
+            self.expect(b';')?;
+
+            fields.insert(key.into_owned(), value);
+        }
+
+        Ok(PBXHashMap::new(fields))
+    }
+
+    /// Parse array values, after the opening `(`
+    fn array(&mut self) -> Result<PBXVec> {
+        let mut values = vec![];
+
+        loop {
+            self.skip_trivia()?;
+            if self.eat(b')') {
+                break;
+            }
+
+            values.push(self.value()?);
+            self.skip_trivia()?;
+            if !self.eat(b',') {
+                self.skip_trivia()?;
+                self.expect(b')')?;
+                break;
+            }
+        }
+
+        Ok(PBXVec::new(values))
+    }
+
+    fn key(&mut self) -> Result<Cow<'a, str>> {
+        match self.peek() {
+            Some(b'"') => self.string(),
+            _ => self.unquoted().map(Cow::Borrowed),
+        }
+    }
+
+    fn value(&mut self) -> Result<PBXValue> {
+        self.skip_trivia()?;
+        match self.peek() {
+            Some(b'{') => {
+                self.position += 1;
+                self.object().map(PBXValue::Object)
+            }
+            Some(b'(') => {
+                self.position += 1;
+                self.array().map(PBXValue::Vec)
+            }
+            Some(b'"') => Ok(PBXValue::String(self.string()?.into_owned())),
+            _ => self.unquoted().map(unquoted_value),
+        }
+    }
+
+    /// Parse quoted string, decoding escape sequences
+    fn string(&mut self) -> Result<Cow<'a, str>> {
+        let start = self.position;
+        let raw = self.quoted()?;
+        unescape_string(raw).map_err(|e| {
+            self.position = start;
+            self.error(e.to_string())
+        })
+    }
+
+    /// Parse quoted string, returning raw content between quotes
+    fn quoted(&mut self) -> Result<&'a str> {
+        self.expect(b'"')?;
+        let start = self.position;
+        let bytes = self.input.as_bytes();
+
+        while let Some(byte) = bytes.get(self.position) {
+            match byte {
+                b'\\' => self.position += 2,
+                b'"' => {
+                    let raw = &self.input[start..self.position];
+                    self.position += 1;
+                    return Ok(raw);
+                }
+                _ => self.position += 1,
+            }
+        }
+
+        self.position = start;
+        Err(self.error("Unterminated string"))
+    }
+
+    fn unquoted(&mut self) -> Result<&'a str> {
+        let start = self.position;
+        let length = self
+            .rest()
+            .bytes()
+            .take_while(|b| b.is_ascii_alphanumeric() || b"_$+/:.-".contains(b))
+            .count();
+
+        if length == 0 {
+            return Err(self.error("Expected value"));
+        }
+
+        self.position += length;
+        Ok(&self.input[start..self.position])
+    }
+
+    fn skip_whitespace(&mut self) {
+        let length = self
+            .rest()
+            .bytes()
+            .take_while(u8::is_ascii_whitespace)
+            .count();
+        self.position += length;
+    }
+
+    /// Skip whitespace and comments
+    fn skip_trivia(&mut self) -> Result<()> {
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if let Some(comment) = rest.strip_prefix("/*") {
+                match comment.find("*/") {
+                    Some(end) => self.position += end + 4,
+                    None => return Err(self.error("Unterminated comment")),
+                }
+            } else if rest.starts_with("//") {
+                self.position += rest.find('\n').unwrap_or(rest.len());
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.position).copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        let matches = self.peek() == Some(byte);
+        if matches {
+            self.position += 1;
+        }
+        matches
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<()> {
+        if self.eat(byte) {
+            Ok(())
+        } else {
+            Err(self.error(format!("Expected `{}`", byte as char)))
+        }
+    }
+
+    fn error<S: AsRef<str>>(&self, message: S) -> anyhow::Error {
+        let consumed = &self.input[..self.position];
+        let line = consumed.matches('\n').count() + 1;
+        let column = consumed.len() - consumed.rfind('\n').map(|i| i + 1).unwrap_or(0) + 1;
+        let found = self.rest().chars().next();
+
+        anyhow!("{} at {line}:{column}, found {found:?}", message.as_ref())
+    }
+}
+
+/// Value of an unquoted token: bool, object kind, number or string.
+pub(crate) fn unquoted_value(token: &str) -> PBXValue {
+    match token {
+        "YES" => return PBXValue::Bool(true),
+        "NO" => return PBXValue::Bool(false),
+        _ if KINDS.contains(&token) => return PBXValue::Kind(PBXObjectKind::from(token)),
+        _ => {}
+    }
+
+    match PBXNumber::new(token) {
+        Some(number) => PBXValue::Number(number),
+        None => PBXValue::String(token.to_string()),
+    }
+}
+
+#[cfg(test)]
+macro_rules! test_file {
+    ($path:expr) => {{
+        use super::*;
+
+        let file = PBXParser::try_parse_from_file($path);
+        if let Err(err) = &file {
+            println!("Error: {err:#?}")
+        }
+        assert!(file.is_ok());
+        file.unwrap()
+    }};
+}
+
+#[cfg(test)]
+mod parse_tests {
+    macro_rules! test_samples {
+        ($($name:ident),*) => {
+            $(#[test]
+                fn $name() {
+                    let (root, name) = (env!("CARGO_MANIFEST_DIR"), stringify!($name));
+                    let path = format!("{root}/tests/samples/{name}.pbxproj");
+                    let file = test_file!(&path);
+                    let expected = crate::pbxproj::pest::PBXProjectParser::try_parse_from_file(&path);
+                    assert_eq!(file, expected.unwrap());
+                })*
+        };
+    }
+
+    test_samples![
+        demo1, demo2, demo3, demo4, demo5, demo6, demo7, demo8, demo9, demo10, demo11, demo12
+    ];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values() {
+        let content = r#"// !$*UTF8*$!
+        {
+            /* comment */
+            archiveVersion = 1;
+            empty = {};
+            list = (a, "b c", 1.2,);
+            isa = PBXGroup;
+            unknown = XCLocalSwiftPackageReference;
+            flag = NO;
+            path = ../Shared/File.swift;
+            "quoted key" = "line\n\"quoted\"";
+        }"#;
+        let object = PBXParser::try_from_str(content).unwrap();
+
+        assert_eq!(object.get_number("archiveVersion").unwrap(), &1);
+        assert_eq!(object.get_object("empty"), Some(&PBXHashMap::default()));
+        assert_eq!(
+            object.get_vec("list").unwrap().as_vec_strings(),
+            vec!["a", "b c"]
+        );
+        assert!(object.get_kind("isa").unwrap().is_pbx_fsreference());
+        assert_eq!(
+            object.get_string("unknown").unwrap(),
+            "XCLocalSwiftPackageReference"
+        );
+        assert_eq!(object.get_bool("flag"), Some(&false));
+        assert_eq!(object.get_string("path").unwrap(), "../Shared/File.swift");
+        assert_eq!(object.get_string("quoted key").unwrap(), "line\n\"quoted\"");
+    }
+
+    #[test]
+    fn numbers() {
+        let content = r#"// !$*UTF8*$!
+        {
+            objectVersion = 56;
+            CURRENT_PROJECT_VERSION = 007;
+            MARKETING_VERSION = 1.10;
+            CreatedOnToolsVersion = 13.2.1;
+            quoted = "0";
+            unquoted = 0;
+        }"#;
+        let object = PBXParser::try_from_str(content).unwrap();
+        let number = |key| object.get_number(key).unwrap();
+
+        assert_eq!(number("objectVersion").as_isize(), Some(56));
+        assert_eq!(number("CURRENT_PROJECT_VERSION").as_str(), "007");
+        assert_eq!(number("CURRENT_PROJECT_VERSION").as_isize(), Some(7));
+        assert_eq!(number("MARKETING_VERSION").to_string(), "1.10");
+        assert_eq!(number("MARKETING_VERSION").parse::<f64>().unwrap(), 1.1);
+        assert_eq!(object.get_str("CreatedOnToolsVersion"), Some("13.2.1"));
+        assert_eq!(number("CreatedOnToolsVersion").as_isize(), None);
+        assert_eq!(object.get_string("quoted").unwrap(), "0");
+        assert_eq!(number("unquoted"), &0);
+        assert_eq!(
+            object,
+            crate::pbxproj::pest::PBXProjectParser::try_from_str(content).unwrap()
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let error = |content| PBXParser::try_from_str(content).unwrap_err().to_string();
+
+        assert_eq!(
+            error("{}"),
+            "Expected `// !$*UTF8*$!` header at 1:1, found Some('{')"
+        );
+        assert_eq!(
+            error("// !$*UTF8*$!\n{\n  key = value\n}"),
+            "Expected `;` at 4:1, found Some('}')"
+        );
+        assert_eq!(
+            error("// !$*UTF8*$!\n{ key = \"value; }"),
+            "Unterminated string at 2:10, found Some('v')"
+        );
+    }
+}
@@ -1,11 +1,11 @@
 #![allow(missing_docs)]
+#![allow(clippy::result_large_err)]
 use super::object::PBXObjectKind;
-use super::{PBXHashMap, PBXVec};
+use super::{unescape_string, PBXHashMap, PBXNumber, PBXVec};
 use crate::pbxproj::PBXValue;
 use anyhow::{anyhow, Context, Result};
 use itertools::Itertools;
 use std::path::Path;
-use std::{collections::HashMap, num::ParseIntError};
 
 use pest_consume::*;
 use tap::Pipe;
@@ -21,13 +21,14 @@ pub(crate) type Node<'i> = pest_consume::Node<'i, Rule, ()>;
 impl PBXProjectParser {
     fn key(input: Node) -> NodeResult<String> {
         let inner = input.into_children().next().unwrap();
-        Ok(inner.as_str().to_string())
+        match inner.as_rule() {
+            Rule::string => Self::unquote(inner),
+            _ => Ok(inner.as_str().to_string()),
+        }
     }
 
     fn string(input: Node) -> NodeResult<PBXValue> {
-        let value = input.as_str().replace("\"", "");
-        // println!("string value: `{value}`");
-        value.pipe(PBXValue::String).pipe(Ok)
+        Self::unquote(input)?.pipe(PBXValue::String).pipe(Ok)
     }
 
     fn kind(input: Node) -> NodeResult<PBXValue> {
@@ -44,14 +45,9 @@ impl PBXProjectParser {
     }
 
     fn number(input: Node) -> NodeResult<PBXValue> {
-        // TODO: identify versions as string instead of number or as ident!
         let value = input.as_str();
-        if value.contains(".") {
-            return Ok(PBXValue::String(value.into()));
-        }
-        value
-            .parse()
-            .map_err(|e: ParseIntError| input.error(e))
+        PBXNumber::new(value)
+            .ok_or_else(|| input.error(format!("{value:?} is not a number!")))
             .map(PBXValue::Number)
     }
 
@@ -100,9 +96,8 @@ impl PBXProjectParser {
 
     fn object(input: Node) -> NodeResult<PBXValue> {
         match_nodes!(input.into_children();
-            [field(fields)..] => fields.collect::<HashMap<String, PBXValue>>(),
+            [field(fields)..] => fields.collect::<PBXHashMap>(),
         )
-        .pipe(PBXHashMap::new)
         .pipe(PBXValue::Object)
         .pipe(Ok)
     }
@@ -114,6 +109,14 @@ impl PBXProjectParser {
 }
 
 impl PBXProjectParser {
+    /// Strip surrounding quotes of a string node and decode its escape sequences
+    fn unquote(input: Node) -> NodeResult<String> {
+        let value = input.as_str();
+        unescape_string(&value[1..value.len() - 1])
+            .map(|v| v.into_owned())
+            .map_err(|e| input.error(e))
+    }
+
     pub fn try_parse_from_file<P>(path: P) -> Result<PBXHashMap>
     where
         P: AsRef<Path> + std::fmt::Debug,
@@ -161,5 +164,18 @@ mod parse_tests {
         };
     }
 
-    test_samples![demo1, demo2, demo3, demo4, demo5, demo6, demo7, demo8, demo9, demo10, demo11, demo12];
+    test_samples![
+        demo1, demo2, demo3, demo4, demo5, demo6, demo7, demo8, demo9, demo10, demo11, demo12
+    ];
+
+    #[test]
+    fn long_alphanumeric_ident() {
+        use super::*;
+
+        // 40 character revision hash, longer than any uuid and with a digit after 32 characters
+        let revision = "0c7a5f1d2e3b4a5968778695a4b3c2d112345678";
+        let content = format!("// !$*UTF8*$!\n{{\n\trevision = {revision};\n}}\n");
+        let file = PBXProjectParser::try_from_str(content).unwrap();
+        assert_eq!(file.get_str("revision"), Some(revision));
+    }
 }
@@ -4,19 +4,22 @@ use derive_deref_rs::Deref;
 use derive_is_enum_variant::is_enum_variant;
 use enum_as_inner::EnumAsInner;
 use enum_variant_macros::FromVariants;
+use indexmap::IndexMap;
 use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
 
 /// Repersentation of all values that can be collected from pbxproj file.
-#[derive(Debug, PartialEq, Eq, FromVariants, EnumAsInner, is_enum_variant)]
+#[derive(Debug, Clone, PartialEq, Eq, FromVariants, EnumAsInner, is_enum_variant)]
 pub enum PBXValue {
     /// Normal String value. NOTE: This may literal string!
     String(String),
-    /// Object value represented as [`HashMap`]
+    /// Object value represented as [`PBXHashMap`]
     Object(PBXHashMap),
     /// Vec of [`PBXValue`]
     Vec(PBXVec),
-    /// Number
-    Number(isize),
+    /// Unquoted numeric scalar, i.e. `56`, `007` or `13.2.1`
+    Number(PBXNumber),
     /// Boolean representation of YES, NO
     Bool(bool),
     /// ObjectKind
@@ -61,7 +64,13 @@ impl TryFrom<PBXValue> for isize {
     type Error = anyhow::Error;
 
     fn try_from(value: PBXValue) -> Result<Self, Self::Error> {
-        value.try_into_number()
+        value.try_into_number()?.parse()
+    }
+}
+
+impl From<isize> for PBXValue {
+    fn from(v: isize) -> Self {
+        PBXValue::Number(v.into())
     }
 }
 
@@ -95,10 +104,7 @@ where
     PBXValue: From<T>,
 {
     fn from(v: HashMap<String, T>) -> Self {
-        let inner = v
-            .into_iter()
-            .map(|(k, v)| (k, v.try_into().unwrap()))
-            .collect();
+        let inner = v.into_iter().map(|(k, v)| (k, v.into())).collect();
 
         PBXValue::Object(PBXHashMap(inner))
     }
@@ -187,7 +193,7 @@ impl PBXValue {
     /// Returns `Ok(T)` if the value is [`Number`].
     ///
     /// [`Number`]: PBXValue::Number
-    pub fn try_into_number(self) -> Result<isize> {
+    pub fn try_into_number(self) -> Result<PBXNumber> {
         if let Self::Number(v) = self {
             Ok(v)
         } else {
@@ -216,22 +222,125 @@ impl PBXValue {
             bail!("expected kind got {self:#?}")
         }
     }
+
+    /// Returns text of [`String`] or lexeme of [`Number`].
+    ///
+    /// Useful for values such as versions, that are only quoted when needed.
+    ///
+    /// [`String`]: PBXValue::String
+    /// [`Number`]: PBXValue::Number
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(v) => Some(v),
+            Self::Number(v) => Some(v.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Returns flag value of [`Bool`], `1`/`0` [`Number`] or their [`String`] spelling.
+    ///
+    /// Flags such as `alwaysOutOfDate` are written as `1`, but can be quoted or spelled `YES`.
+    ///
+    /// [`Bool`]: PBXValue::Bool
+    /// [`Number`]: PBXValue::Number
+    /// [`String`]: PBXValue::String
+    pub fn as_flag(&self) -> Option<bool> {
+        match self.as_str() {
+            Some("1" | "YES" | "true") => Some(true),
+            Some("0" | "NO" | "false") => Some(false),
+            _ => self.as_bool().copied(),
+        }
+    }
 }
 
-/// [`HashMap`] wrapper for [`PBXValue`] with helpers
-#[derive(Default, Debug, Deref, PartialEq, Eq)]
-pub struct PBXHashMap(pub(crate) HashMap<String, PBXValue>);
+/// Unquoted numeric scalar, kept as it is written in pbxproj file.
+///
+/// Values such as `007` or `1.10` are not normalized, use [`PBXNumber::parse`] or
+/// [`PBXNumber::as_isize`] to interpret them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PBXNumber(String);
+
+impl PBXNumber {
+    /// Create new number from its lexeme, returning None if it isn't numeric.
+    pub fn new<S: Into<String>>(lexeme: S) -> Option<Self> {
+        let lexeme = lexeme.into();
+        let is_numeric = lexeme
+            .split('.')
+            .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()));
+        is_numeric.then_some(Self(lexeme))
+    }
+
+    /// Original lexeme
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Interpret as isize, returning None for versions or out of range values
+    pub fn as_isize(&self) -> Option<isize> {
+        self.parse().ok()
+    }
+
+    /// Interpret as a given type
+    pub fn parse<F>(&self) -> Result<F>
+    where
+        F: FromStr,
+        F::Err: Display,
+    {
+        self.0
+            .parse()
+            .map_err(|e| anyhow::anyhow!("{:?} is not parseable: {e}", self.0))
+    }
+}
+
+impl From<isize> for PBXNumber {
+    fn from(v: isize) -> Self {
+        Self(v.to_string())
+    }
+}
+
+impl PartialEq<isize> for PBXNumber {
+    fn eq(&self, other: &isize) -> bool {
+        self.as_isize() == Some(*other)
+    }
+}
+
+impl Display for PBXNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Insertion ordered map of [`PBXValue`] with helpers.
+///
+/// Keys are kept in the order they were parsed or inserted in, so iteration and `Debug`
+/// output are deterministic and match the source file.
+#[derive(Default, Debug, Clone, Deref, PartialEq, Eq)]
+pub struct PBXHashMap(pub(crate) IndexMap<String, PBXValue>);
 
 impl PBXHashMap {
     /// ...
-    pub fn new(inner: HashMap<String, PBXValue>) -> Self {
+    pub fn new(inner: IndexMap<String, PBXValue>) -> Self {
         Self(inner)
     }
+
+    /// Insert value with a given key, keeping position of an existing key.
+    pub fn insert_value<K: Into<String>, V: Into<PBXValue>>(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> Option<PBXValue> {
+        self.0.insert(key.into(), value.into())
+    }
     /// Get Value from map
     pub fn get_value(&self, key: &str) -> Option<&PBXValue> {
         self.0.get(key)
     }
 
+    /// Get mutable value from map
+    pub fn get_value_mut(&mut self, key: &str) -> Option<&mut PBXValue> {
+        self.0.get_mut(key)
+    }
+
     /// Try Get Value from map or error
     pub fn try_get_value(&self, key: &str) -> Result<&PBXValue> {
         self.0
@@ -244,25 +353,49 @@ impl PBXHashMap {
         self.try_remove_value(key).ok()
     }
 
-    /// Try remove value from map
+    /// Try remove value from map, keeping order of remaining keys
     pub fn try_remove_value(&mut self, key: &str) -> Result<PBXValue> {
         self.0
-            .remove(key)
+            .shift_remove(key)
             .ok_or_else(|| anyhow::anyhow!("{key} is not found!"))
     }
+
+    /// Get string or number lexeme, see [`PBXValue::as_str`]
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.0.get(key)?.as_str()
+    }
+
+    /// Get flag, see [`PBXValue::as_flag`]
+    pub fn get_flag(&self, key: &str) -> Option<bool> {
+        self.0.get(key)?.as_flag()
+    }
+
+    /// Try get string or number lexeme, see [`PBXValue::as_str`]
+    pub fn try_get_str(&self, key: &str) -> Result<&str> {
+        let value = self.try_get_value(key)?;
+        value
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("expected value to be str, got {value:?}"))
+    }
+}
+
+impl FromIterator<(String, PBXValue)> for PBXHashMap {
+    fn from_iter<I: IntoIterator<Item = (String, PBXValue)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
 }
 
 gen_hash_map_helpers! {
     [string, String],
     [vec, PBXVec],
     [bool, bool],
-    [number, isize],
+    [number, PBXNumber],
     [kind, PBXObjectKind],
     [object, PBXHashMap]
 }
 
 /// [`Vec`] wrapper for [`PBXValue`] with helpers
-#[derive(Default, Debug, Deref, PartialEq, Eq, derive_new::new)]
+#[derive(Default, Debug, Clone, Deref, PartialEq, Eq, derive_new::new)]
 pub struct PBXVec(pub(crate) Vec<PBXValue>);
 
 impl PBXVec {
@@ -274,7 +407,7 @@ impl PBXVec {
         Ok(collector)
     }
 
-    pub(crate) fn as_vec_strings<'a>(&'a self) -> Vec<&'a String> {
+    pub(crate) fn as_vec_strings(&self) -> Vec<&String> {
         let mut collector = vec![];
         for value in self.0.iter() {
             if let Some(str) = value.as_string() {
@@ -287,7 +420,7 @@ impl PBXVec {
     pub(crate) fn try_into_vec<T: TryFrom<T> + From<PBXValue>>(self) -> Result<Vec<T>> {
         let mut collector = vec![];
         for value in self.0 {
-            collector.push(value.try_into()?);
+            collector.push(value.into());
         }
         Ok(collector)
     }
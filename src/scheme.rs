@@ -15,14 +15,31 @@ pub struct XCScheme {
     pub version: Option<String>,
     /// ...
     pub was_created_for_app_extension: Option<bool>,
+    /// Targets referenced by scheme actions
+    #[serde(skip)]
+    pub buildable_references: Vec<XCBuildableReference>,
+}
+
+/// Reference to a buildable target in a scheme action
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct XCBuildableReference {
+    /// Target id (`BlueprintIdentifier`)
+    pub blueprint_identifier: Option<String>,
+    /// Target name (`BlueprintName`)
+    pub blueprint_name: Option<String>,
+    /// Product name (`BuildableName`)
+    pub buildable_name: Option<String>,
+    /// Container of the target, i.e. `container:App.xcodeproj`
+    pub referenced_container: Option<String>,
 }
 
 impl XCScheme {
     /// Read and parse *.xcscheme content
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
         let path = path.as_ref();
-        let content = std::fs::read_to_string(&path)?;
+        let content = std::fs::read_to_string(path)?;
         let mut scheme = serde_xml_rs::from_str::<Self>(&content)?;
+        scheme.buildable_references = buildable_references(&content)?;
         scheme.name = path
             .file_name()
             .and_then(|s| s.to_str())
@@ -33,6 +50,49 @@ impl XCScheme {
             .into();
         Ok(scheme)
     }
+
+    /// Whether any scheme action references target with a given id and name.
+    ///
+    /// References without `BlueprintIdentifier` are matched by name.
+    pub fn references_target(&self, id: &str, name: &str) -> bool {
+        self.buildable_references.iter().any(|reference| {
+            match reference.blueprint_identifier.as_deref() {
+                Some(identifier) => identifier == id,
+                None => reference.blueprint_name.as_deref() == Some(name),
+            }
+        })
+    }
+}
+
+/// Collect BuildableReference elements of xcscheme content, skipping duplicates
+fn buildable_references(content: &str) -> Result<Vec<XCBuildableReference>> {
+    use xml::reader::{EventReader, XmlEvent};
+
+    let mut references = vec![];
+    for event in EventReader::from_str(content) {
+        let attributes = match event? {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } if name.local_name == "BuildableReference" => attributes,
+            _ => continue,
+        };
+        let attribute = |key: &str| {
+            attributes
+                .iter()
+                .find(|a| a.name.local_name == key)
+                .map(|a| a.value.clone())
+        };
+        let reference = XCBuildableReference {
+            blueprint_identifier: attribute("BlueprintIdentifier"),
+            blueprint_name: attribute("BlueprintName"),
+            buildable_name: attribute("BuildableName"),
+            referenced_container: attribute("ReferencedContainer"),
+        };
+        if !references.contains(&reference) {
+            references.push(reference);
+        }
+    }
+    Ok(references)
 }
 
 #[cfg(test)]
@@ -62,4 +122,24 @@ mod tests {
     }
 
     test_samples![demo1];
+
+    #[test]
+    fn buildable_references() {
+        let root = env!("CARGO_MANIFEST_DIR");
+        let scheme = super::XCScheme::new(format!("{root}/tests/schemes/demo1.xcscheme")).unwrap();
+        let names = scheme
+            .buildable_references
+            .iter()
+            .map(|r| r.blueprint_name.as_deref().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["demo1", "NoBlueprintIDTests"]);
+        assert_eq!(
+            scheme.buildable_references[0]
+                .referenced_container
+                .as_deref(),
+            Some("container:demo1.xcodeproj")
+        );
+        assert!(scheme.references_target("A1B2", "NoBlueprintIDTests"));
+        assert!(!scheme.references_target("A1B2", "Other"));
+    }
 }